@@ -0,0 +1,374 @@
+use syn::punctuated::Punctuated;
+use syn::{
+    AttrStyle, Attribute, Expr, ForeignItem, ImplItem, Item, Lit, Meta, Path, Token, TraitItem,
+};
+
+use crate::model::{AttributeJson, CfgJson};
+use crate::pretty::pretty_tokens;
+
+/// the `attrs` field shared by the listed variants, or no attributes for any other variant
+macro_rules! variant_attrs {
+    ($value:expr, $enum:ident => [$($variant:ident),* $(,)?]) => {
+        match $value {
+            $($enum::$variant(inner) => &inner.attrs,)*
+            _ => &[],
+        }
+    };
+}
+
+/// the `attrs` field shared by the listed variants, or None for any other variant
+macro_rules! variant_attrs_mut {
+    ($value:expr, $enum:ident => [$($variant:ident),* $(,)?]) => {
+        match $value {
+            $($enum::$variant(inner) => Some(&mut inner.attrs),)*
+            _ => None,
+        }
+    };
+}
+
+/// build_attributes
+///
+/// # Arguments
+/// * `attrs`: &[Attribute] - the attributes of a syntax node
+///
+/// # Returns
+/// * `(Vec<AttributeJson>, Option<String>)` - every attribute in source order, doc comments
+///   included, and the text of the doc comments joined with newlines, or None when there are none
+pub(crate) fn build_attributes(attrs: &[Attribute]) -> (Vec<AttributeJson>, Option<String>) {
+    let attributes: Vec<AttributeJson> = attrs.iter().map(build_attribute).collect();
+    let docs = joined_docs(&attributes);
+    (attributes, docs)
+}
+
+/// joined_docs
+///
+/// # Arguments
+/// * `attributes`: &[AttributeJson] - the attributes of a node
+///
+/// # Returns
+/// * `Option<String>` - the text of the doc attributes joined with newlines, without the space
+///   that usually follows `///`, or None when there are none
+pub(crate) fn joined_docs(attributes: &[AttributeJson]) -> Option<String> {
+    let docs: Vec<&str> = attributes
+        .iter()
+        .filter_map(|attr| attr.doc.as_deref())
+        .map(|text| text.strip_prefix(' ').unwrap_or(text))
+        .collect();
+
+    (!docs.is_empty()).then(|| docs.join("\n"))
+}
+
+/// doc_text
+///
+/// # Arguments
+/// * `attr`: &Attribute - an attribute
+///
+/// # Returns
+/// * `Option<String>` - the text of a `///` or `#[doc = "..."]` line exactly as written,
+///   including the space that usually follows `///`, or None for any other attribute
+fn doc_text(attr: &Attribute) -> Option<String> {
+    if let Meta::NameValue(name_value) = &attr.meta
+        && name_value.path.is_ident("doc")
+        && let Expr::Lit(expr_lit) = &name_value.value
+        && let Lit::Str(lit_str) = &expr_lit.lit
+    {
+        return Some(lit_str.value());
+    }
+    None
+}
+
+/// build_attribute
+///
+/// # Arguments
+/// * `attr`: &Attribute - an attribute
+///
+/// # Returns
+/// * `AttributeJson` - the model of the attribute
+fn build_attribute(attr: &Attribute) -> AttributeJson {
+    let path = attr.path();
+    if let Some(doc) = doc_text(attr) {
+        return AttributeJson {
+            path: pretty_tokens(path),
+            inner: matches!(attr.style, AttrStyle::Inner(_)),
+            arguments: None,
+            value: None,
+            derives: Vec::new(),
+            cfg: None,
+            doc: Some(doc),
+        };
+    }
+
+    let (arguments, value) = match &attr.meta {
+        Meta::Path(_) => (None, None),
+        Meta::List(list) => (Some(pretty_tokens(&list.tokens)), None),
+        Meta::NameValue(name_value) => (None, Some(pretty_tokens(&name_value.value))),
+    };
+
+    let derives = if path.is_ident("derive") {
+        attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+            .map(|paths| paths.iter().map(pretty_tokens).collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // `cfg_attr` applies its remaining arguments only when its first argument holds
+    let cfg = if path.is_ident("cfg") || path.is_ident("cfg_attr") {
+        let predicate = attr
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .ok()
+            .and_then(|metas| metas.into_iter().next());
+        Some(match predicate {
+            Some(meta) => build_cfg(&meta),
+            None => CfgJson::Other {
+                description: arguments.clone().unwrap_or_default(),
+            },
+        })
+    } else {
+        None
+    };
+
+    AttributeJson {
+        path: pretty_tokens(path),
+        inner: matches!(attr.style, AttrStyle::Inner(_)),
+        arguments,
+        value,
+        derives,
+        cfg,
+        doc: None,
+    }
+}
+
+/// build_cfg
+///
+/// # Arguments
+/// * `meta`: &Meta - a configuration predicate
+///
+/// # Returns
+/// * `CfgJson` - the predicate as a tree of `all`, `any`, `not` and options
+fn build_cfg(meta: &Meta) -> CfgJson {
+    match meta {
+        Meta::Path(path) => CfgJson::Option {
+            name: pretty_tokens(path),
+            value: None,
+        },
+        Meta::NameValue(name_value) => CfgJson::Option {
+            name: pretty_tokens(&name_value.path),
+            value: Some(match &name_value.value {
+                Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    Lit::Str(lit_str) => lit_str.value(),
+                    lit => pretty_tokens(lit),
+                },
+                value => pretty_tokens(value),
+            }),
+        },
+        Meta::List(list) => {
+            let predicates: Vec<CfgJson> = list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .map(|metas| metas.iter().map(build_cfg).collect())
+                .unwrap_or_default();
+
+            if list.path.is_ident("all") {
+                CfgJson::All { predicates }
+            } else if list.path.is_ident("any") {
+                CfgJson::Any { predicates }
+            } else if list.path.is_ident("not") && predicates.len() == 1 {
+                CfgJson::Not {
+                    predicate: Box::new(predicates.into_iter().next().unwrap()),
+                }
+            } else {
+                CfgJson::Other {
+                    description: pretty_tokens(meta),
+                }
+            }
+        }
+    }
+}
+
+/// item_attributes
+///
+/// # Arguments
+/// * `item`: &Item - an item
+///
+/// # Returns
+/// * `&[Attribute]` - the attributes written on the item
+pub(crate) fn item_attributes(item: &Item) -> &[Attribute] {
+    variant_attrs!(item, Item => [
+        Const, Enum, ExternCrate, Fn, ForeignMod, Impl, Macro, Mod, Static, Struct, Trait,
+        TraitAlias, Type, Union, Use,
+    ])
+}
+
+/// item_without_outer_attributes
+///
+/// # Arguments
+/// * `item`: &Item - an item
+///
+/// # Returns
+/// * `Item` - a copy of the item without its outer attributes, which the model keeps separately
+pub(crate) fn item_without_outer_attributes(item: &Item) -> Item {
+    let mut item = item.clone();
+    let attrs = variant_attrs_mut!(&mut item, Item => [
+        Const, Enum, ExternCrate, Fn, ForeignMod, Impl, Macro, Mod, Static, Struct, Trait,
+        TraitAlias, Type, Union, Use,
+    ]);
+    if let Some(attrs) = attrs {
+        retain_inner(attrs);
+    }
+    item
+}
+
+/// impl_item_attributes
+///
+/// # Arguments
+/// * `item`: &ImplItem - an associated item of an impl block
+///
+/// # Returns
+/// * `&[Attribute]` - the attributes written on the associated item
+pub(crate) fn impl_item_attributes(item: &ImplItem) -> &[Attribute] {
+    variant_attrs!(item, ImplItem => [Const, Fn, Type, Macro])
+}
+
+/// trait_item_attributes
+///
+/// # Arguments
+/// * `item`: &TraitItem - an associated item of a trait
+///
+/// # Returns
+/// * `&[Attribute]` - the attributes written on the associated item
+pub(crate) fn trait_item_attributes(item: &TraitItem) -> &[Attribute] {
+    variant_attrs!(item, TraitItem => [Const, Fn, Type, Macro])
+}
+
+/// foreign_item_attributes
+///
+/// # Arguments
+/// * `item`: &ForeignItem - an item declared inside an `extern` block
+///
+/// # Returns
+/// * `&[Attribute]` - the attributes written on the foreign item
+pub(crate) fn foreign_item_attributes(item: &ForeignItem) -> &[Attribute] {
+    variant_attrs!(item, ForeignItem => [Fn, Static, Type, Macro])
+}
+
+/// expr_attributes
+///
+/// # Arguments
+/// * `expr`: &Expr - an expression
+///
+/// # Returns
+/// * `&[Attribute]` - the attributes written on the expression
+pub(crate) fn expr_attributes(expr: &Expr) -> &[Attribute] {
+    variant_attrs!(expr, Expr => [
+        Array, Assign, Async, Await, Binary, Block, Break, Call, Cast, Closure, Const, Continue,
+        Field, ForLoop, Group, If, Index, Infer, Let, Lit, Loop, Macro, Match, MethodCall, Paren,
+        Path, Range, RawAddr, Reference, Repeat, Return, Struct, Try, TryBlock, Tuple, Unary,
+        Unsafe, While, Yield,
+    ])
+}
+
+/// expr_without_outer_attributes
+///
+/// # Arguments
+/// * `expr`: &Expr - an expression
+///
+/// # Returns
+/// * `Expr` - a copy of the expression without its outer attributes, which the model keeps separately
+pub(crate) fn expr_without_outer_attributes(expr: &Expr) -> Expr {
+    let mut expr = expr.clone();
+    let attrs = variant_attrs_mut!(&mut expr, Expr => [
+        Array, Assign, Async, Await, Binary, Block, Break, Call, Cast, Closure, Const, Continue,
+        Field, ForLoop, Group, If, Index, Infer, Let, Lit, Loop, Macro, Match, MethodCall, Paren,
+        Path, Range, RawAddr, Reference, Repeat, Return, Struct, Try, TryBlock, Tuple, Unary,
+        Unsafe, While, Yield,
+    ]);
+    if let Some(attrs) = attrs {
+        retain_inner(attrs);
+    }
+    expr
+}
+
+/// retain_inner
+///
+/// # Arguments
+/// * `attrs`: &mut Vec<Attribute> - the attributes of a syntax node
+///
+/// # Returns
+/// * `()`
+pub(crate) fn retain_inner(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| matches!(attr.style, AttrStyle::Inner(_)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{ItemStruct, parse_quote};
+
+    #[test]
+    fn test_derives_and_docs() {
+        let item: ItemStruct = parse_quote! {
+            /// A point
+            ///
+            /// on a plane
+            #[derive(Debug, Clone, serde::Serialize)]
+            #[repr(C)]
+            struct Point;
+        };
+
+        let (attributes, docs) = build_attributes(&item.attrs);
+        assert_eq!(docs.as_deref(), Some("A point\n\non a plane"));
+        assert_eq!(attributes.len(), 5);
+        assert_eq!(attributes[0].path, "doc");
+        assert_eq!(attributes[0].doc.as_deref(), Some(" A point"));
+        assert_eq!(attributes[1].doc.as_deref(), Some(""));
+        assert_eq!(
+            attributes[3].derives,
+            vec!["Debug", "Clone", "serde::Serialize"]
+        );
+        assert_eq!(attributes[4].path, "repr");
+        assert_eq!(attributes[4].arguments.as_deref(), Some("C"));
+    }
+
+    #[test]
+    fn test_docs_keep_position_and_text() {
+        let item: ItemStruct = parse_quote! {
+            #[derive(Debug)]
+            ///no space
+            #[allow(dead_code)]
+            struct Point;
+        };
+
+        let (attributes, docs) = build_attributes(&item.attrs);
+        assert_eq!(docs.as_deref(), Some("no space"));
+        assert_eq!(attributes[0].path, "derive");
+        assert_eq!(attributes[1].doc.as_deref(), Some("no space"));
+        assert_eq!(attributes[2].path, "allow");
+    }
+
+    #[test]
+    fn test_cfg_tree() {
+        let item: ItemStruct = parse_quote! {
+            #[cfg(all(unix, not(feature = "serde")))]
+            struct Unix;
+        };
+
+        let (attributes, _) = build_attributes(&item.attrs);
+        assert_eq!(
+            attributes[0].cfg,
+            Some(CfgJson::All {
+                predicates: vec![
+                    CfgJson::Option {
+                        name: "unix".to_string(),
+                        value: None,
+                    },
+                    CfgJson::Not {
+                        predicate: Box::new(CfgJson::Option {
+                            name: "feature".to_string(),
+                            value: Some("serde".to_string()),
+                        }),
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -1,27 +1,50 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 
 use clap::{ArgGroup, Parser};
-use rusty_ast::{JsonVisitor, TextVisitor, parse_rust_file, parse_rust_source};
-use syn::visit::Visit;
-use walkdir::WalkDir;
+use ignore::overrides::OverrideBuilder;
+use ignore::{DirEntry, WalkBuilder};
+use rayon::prelude::*;
+use rusty_ast::model::{FileEntryJson, FileResultJson};
+use rusty_ast::{
+    AstJson, FragmentKind, TextVisitor, parse_rust_file, parse_rust_fragment, parse_rust_source,
+};
 
 /// Tool for parsing Rust code and displaying its AST
 ///
 /// # Arguments
-/// * `file`: &str - path to the rust source file
+/// * `file`: &str - path to the rust source file, or `-` to read it from stdin
+/// * `stdin`: bool - whether to read the rust source from stdin
 /// * `code`: &str - rust source code
 /// * `directory`: &str - path to directory containing rust source files
-/// * `format`: &str - output format (text or json)
+/// * `from_json`: &str - path to a JSON file written by `--format json`
+/// * `format`: &str - output format (text, json, ndjson or rust)
+/// * `kind`: &str - the syntax to parse `--file` or `--code` as, instead of a whole file
 /// * `recursive`: bool - whether to search directories recursively
+/// * `max_depth`: usize - how many levels of directories to descend into
+/// * `include`: Vec<String> - globs a file must match to be processed
+/// * `exclude`: Vec<String> - globs of files and directories to skip
+/// * `no_ignore`: bool - whether to walk into ignored, hidden and target directories
+/// * `follow_links`: bool - whether to follow symbolic links
+/// * `spans`: bool - whether to show source locations in text output
+/// * `output`: &str - path to write the output to instead of stdout
+/// * `out_dir`: &str - directory to write one output file per input file to in directory mode
+/// * `aggregate`: bool - whether to write a directory as one JSON document keyed by file path
+/// * `jobs`: usize - how many files of a directory to parse at once
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
-#[command(group(ArgGroup::new("input").required(true).args(["file", "code", "directory"])))]
+#[command(group(ArgGroup::new("input").required(true).args(["file", "stdin", "code", "directory", "from_json"])))]
 struct Cli {
-    /// Path to the Rust source file to parse
+    /// Path to the Rust source file to parse, or `-` to read it from stdin
     #[arg(short, long, value_name = "FILE")]
     file: Option<PathBuf>,
 
+    /// Read the Rust source to parse from stdin, same as `--file -`
+    #[arg(long)]
+    stdin: bool,
+
     /// Rust code to parse (string)
     #[arg(short, long, value_name = "CODE")]
     code: Option<String>,
@@ -30,13 +53,61 @@ struct Cli {
     #[arg(short = 'd', long, value_name = "DIRECTORY")]
     directory: Option<PathBuf>,
 
-    /// Output format (text or json)
+    /// JSON file written by `--format json` to read back (`-` for stdin), for example to turn it into Rust with `--format rust`
+    #[arg(long, value_name = "FILE")]
+    from_json: Option<PathBuf>,
+
+    /// Output format (text, json, ndjson or rust)
     #[arg(short = 'o', long, value_enum, default_value = "text")]
     format: OutputFormat,
 
+    /// Parse the file or code as a fragment (expr, type, item, stmt, pat, block or attr) instead of a whole file
+    #[arg(short = 'k', long, value_enum, conflicts_with_all = ["directory", "from_json"])]
+    kind: Option<Kind>,
+
     /// Recursively process directories (only applies with --directory)
     #[arg(short = 'r', long)]
     recursive: bool,
+
+    /// Descend at most this many directory levels; 1 only reads the directory itself (overrides --recursive)
+    #[arg(long, value_name = "N")]
+    max_depth: Option<usize>,
+
+    /// Only process files matching this glob, relative to the directory (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Skip files and directories matching this glob, relative to the directory (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Also walk files excluded by .gitignore and .ignore, hidden files and Cargo target directories
+    #[arg(long)]
+    no_ignore: bool,
+
+    /// Follow symbolic links to files and directories
+    #[arg(short = 'L', long)]
+    follow_links: bool,
+
+    /// Show source locations (line:column) in text output; JSON output always includes them
+    #[arg(long)]
+    spans: bool,
+
+    /// Write the output to this file instead of stdout
+    #[arg(long, value_name = "PATH", conflicts_with = "out_dir")]
+    output: Option<PathBuf>,
+
+    /// Write one output file per Rust file into this directory, mirroring the source tree (only with --directory)
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["file", "stdin", "code", "from_json"])]
+    out_dir: Option<PathBuf>,
+
+    /// Write a directory as one JSON document keyed by file path, with parse errors recorded inline (only with --directory and --format json)
+    #[arg(long, conflicts_with_all = ["file", "stdin", "code", "from_json", "out_dir"])]
+    aggregate: bool,
+
+    /// Parse this many files of a directory in parallel; output stays sorted by path [default: number of CPUs]
+    #[arg(short = 'j', long, value_name = "N", conflicts_with_all = ["file", "stdin", "code", "from_json"])]
+    jobs: Option<usize>,
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -45,65 +116,377 @@ enum OutputFormat {
     Text,
     /// JSON format
     Json,
+    /// Newline-delimited JSON, one compact object per line (one per file in directory mode)
+    Ndjson,
+    /// Rust source code generated from the model
+    Rust,
+}
+
+impl OutputFormat {
+    /// The extension appended to the name of an input file to name its output file
+    ///
+    /// # Returns
+    /// * `Option<&str>` - the extension, or None when the output keeps the input name
+    fn extension(&self) -> Option<&'static str> {
+        match self {
+            OutputFormat::Text => Some("txt"),
+            OutputFormat::Json => Some("json"),
+            OutputFormat::Ndjson => Some("ndjson"),
+            OutputFormat::Rust => None,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum Kind {
+    /// An expression, as in `a + b * c`
+    Expr,
+    /// A type, as in `Vec<&'a str>`
+    Type,
+    /// An item, as in `struct Point { x: f64 }`
+    Item,
+    /// A statement, as in `let x = 1;`
+    Stmt,
+    /// A pattern, as in `Some((a, _)) | None`
+    Pat,
+    /// The statements of a block without the braces
+    Block,
+    /// Attributes and doc comments, as in `#[derive(Debug)]`
+    Attr,
+}
+
+impl From<Kind> for FragmentKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Expr => FragmentKind::Expr,
+            Kind::Type => FragmentKind::Type,
+            Kind::Item => FragmentKind::Item,
+            Kind::Stmt => FragmentKind::Stmt,
+            Kind::Pat => FragmentKind::Pat,
+            Kind::Block => FragmentKind::Block,
+            Kind::Attr => FragmentKind::Attr,
+        }
+    }
+}
+
+/// Read an input file named on the command line
+///
+/// # Arguments
+/// * `path`: &Path - path to the file, or `-` for stdin
+///
+/// # Returns
+/// * `io::Result<String>` - the contents of the file
+fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Open the sink the output is written to
+///
+/// # Arguments
+/// * `path`: Option<&Path> - path to the output file, or None for stdout
+///
+/// # Returns
+/// * `io::Result<Box<dyn Write>>` - the sink
+fn open_output(path: Option<&Path>) -> io::Result<Box<dyn Write>> {
+    match path {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            Ok(Box::new(BufWriter::new(fs::File::create(path)?)))
+        }
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// Compute the output path of a file found in directory mode
+///
+/// # Arguments
+/// * `directory`: &Path - the directory being processed
+/// * `path`: &Path - a Rust file found inside the directory
+/// * `out_dir`: &Path - the directory the outputs are written to
+/// * `format`: &OutputFormat - output format, which decides the extension
+///
+/// # Returns
+/// * `PathBuf` - the path mirroring `path` under `out_dir`, as in `out/a/b.rs.json` for `src/a/b.rs`
+fn mirrored_path(directory: &Path, path: &Path, out_dir: &Path, format: &OutputFormat) -> PathBuf {
+    let relative = path.strip_prefix(directory).unwrap_or(path);
+    let mut output = out_dir.join(relative).into_os_string();
+    if let Some(extension) = format.extension() {
+        output.push(".");
+        output.push(extension);
+    }
+    PathBuf::from(output)
+}
+
+/// Write the Rust source code generated from a model
+///
+/// # Arguments
+/// * `ast`: &AstJson - the model to generate code from
+/// * `out`: &mut dyn Write - the sink to write to
+///
+/// # Returns
+/// * `io::Result<()>` - result
+fn write_rust(ast: &AstJson, out: &mut dyn Write) -> io::Result<()> {
+    let source = ast
+        .to_source()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write!(out, "{}", source)
+}
+
+/// Write the model of a file in the requested format
+///
+/// # Arguments
+/// * `ast`: &AstJson - the model of the file
+/// * `path`: Option<&Path> - the path of the file, named in the text heading
+/// * `format`: &OutputFormat - output format (text, json, ndjson or rust)
+/// * `spans`: bool - whether to show source locations in text output
+/// * `out`: &mut dyn Write - the sink to write to
+///
+/// # Returns
+/// * `io::Result<()>` - result
+fn write_ast(
+    ast: &AstJson,
+    path: Option<&Path>,
+    format: &OutputFormat,
+    spans: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Text => {
+            match path {
+                Some(path) => writeln!(out, "AST for Rust code in {}:", path.display())?,
+                None => writeln!(out, "AST for Rust code:")?,
+            }
+            let mut visitor = TextVisitor::with_writer(&mut *out).with_spans(spans);
+            visitor.render_ast(ast)?;
+        }
+        OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(ast)?)?,
+        OutputFormat::Ndjson => {
+            // in directory mode each line also names its file, see `FileEntryJson`
+            match path {
+                Some(path) => {
+                    let entry = FileEntryJson {
+                        path: path.display().to_string(),
+                        result: FileResultJson::Ast(ast.clone()),
+                    };
+                    writeln!(out, "{}", serde_json::to_string(&entry)?)?
+                }
+                None => writeln!(out, "{}", serde_json::to_string(ast)?)?,
+            }
+        }
+        OutputFormat::Rust => write_rust(ast, out)?,
+    }
+    out.flush()
+}
+
+/// How the Rust files of a directory are found
+///
+/// # Fields
+/// * `max_depth`: Option<usize> - how many levels of directories to descend into, None for no limit
+/// * `include`: Vec<String> - globs a file must match, when there are any
+/// * `exclude`: Vec<String> - globs of files and directories to skip
+/// * `no_ignore`: bool - whether to walk into ignored, hidden and target directories
+/// * `follow_links`: bool - whether to follow symbolic links
+#[derive(Default)]
+struct WalkOptions {
+    max_depth: Option<usize>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    no_ignore: bool,
+    follow_links: bool,
+}
+
+/// Check whether a directory entry is a Cargo target directory
+///
+/// # Arguments
+/// * `entry`: &DirEntry - a directory entry
+///
+/// # Returns
+/// * `bool` - whether the entry is a directory holding the `CACHEDIR.TAG` Cargo writes into `target`
+fn is_cargo_target(entry: &DirEntry) -> bool {
+    entry
+        .file_type()
+        .is_some_and(|file_type| file_type.is_dir())
+        && entry.path().join("CACHEDIR.TAG").is_file()
+}
+
+/// Find the Rust files of a directory
+///
+/// # Arguments
+/// * `directory`: &Path - path to the directory
+/// * `walk`: &WalkOptions - how to walk the directory
+///
+/// # Returns
+/// * `io::Result<Vec<PathBuf>>` - the paths of the Rust files sorted by path, or an error for an invalid glob
+fn rust_files(directory: &Path, walk: &WalkOptions) -> io::Result<Vec<PathBuf>> {
+    // Overrides treat plain globs as a whitelist and `!` globs as ignores
+    let mut overrides = OverrideBuilder::new(directory);
+    for glob in &walk.include {
+        overrides
+            .add(glob)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    }
+    for glob in &walk.exclude {
+        overrides
+            .add(&format!("!{}", glob))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    }
+    let overrides = overrides
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut builder = WalkBuilder::new(directory);
+    builder
+        .standard_filters(!walk.no_ignore)
+        .require_git(false)
+        .max_depth(walk.max_depth)
+        .follow_links(walk.follow_links)
+        .overrides(overrides);
+    if !walk.no_ignore {
+        builder.filter_entry(|entry| !is_cargo_target(entry));
+    }
+
+    let mut files: Vec<PathBuf> = builder
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(DirEntry::into_path)
+        .collect();
+    files.sort();
+    Ok(files)
 }
 
+/// How many files each thread parses per chunk of a directory run
+const FILES_PER_THREAD: usize = 4;
+
 /// Process a directory and parse all Rust files
 ///
+/// Files are parsed in parallel on the global rayon thread pool, in chunks of a few files per
+/// thread, and each chunk is written in path order before the next one is parsed.
+///
 /// # Arguments
 /// * `directory`: &Path - path to the directory
-/// * `format`: &OutputFormat - output format (text or json)
-/// * `recursive`: bool - whether to search subdirectories
+/// * `format`: &OutputFormat - output format (text, json or rust)
+/// * `walk`: &WalkOptions - how to find the Rust files of the directory
+/// * `spans`: bool - whether to show source locations in text output
+/// * `out_dir`: Option<&Path> - directory to write one output file per Rust file to, or None for `out`
+/// * `aggregate`: bool - whether to write one JSON document keyed by file path once every file is parsed
+/// * `out`: &mut dyn Write - the sink to write to when there is no `out_dir`
 ///
 /// # Returns
 /// * `io::Result<()>` - result
-fn process_directory(directory: &Path, format: &OutputFormat, recursive: bool) -> io::Result<()> {
+fn process_directory(
+    directory: &Path,
+    format: &OutputFormat,
+    walk: &WalkOptions,
+    spans: bool,
+    out_dir: Option<&Path>,
+    aggregate: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
     // Counter for processed files
     let mut processed_files = 0;
 
-    // Walk the directory
-    let walker = if recursive {
-        WalkDir::new(directory)
-    } else {
-        WalkDir::new(directory).max_depth(1)
-    };
+    // Files by path for the aggregated document, which keeps parse errors inline
+    let mut files = BTreeMap::new();
+
+    // Walk the directory; only Rust files are returned, sorted by path
+    let paths = rust_files(directory, walk)?;
+
+    // Parse the files in parallel, one chunk at a time, and write each chunk in path order
+    // before starting the next, so that only a chunk of models is held in memory at once
+    let chunk_size = rayon::current_num_threads() * FILES_PER_THREAD;
+    for chunk in paths.chunks(chunk_size) {
+        // The model is built on the thread that parsed the file, because span locations are
+        // only known there; the spans are then dropped, since every file adds to that thread's
+        // source map and makes later lookups slower
+        let results: Vec<Result<AstJson, String>> = chunk
+            .par_iter()
+            .map(|path| {
+                let result = parse_rust_file(path)
+                    .map(|ast| AstJson::from_file(&ast))
+                    .map_err(|e| e.to_string());
+                proc_macro2::extra::invalidate_current_thread_spans();
+                result
+            })
+            .collect();
 
-    for entry in walker.into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
+        for (path, result) in chunk.iter().zip(results) {
+            let path = path.as_path();
 
-        // Only process Rust files
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-            println!("\n--- Processing file: {} ---", path.display());
+            // Banners go to stderr so that stdout only carries the output itself
+            eprintln!("\n--- Processing file: {} ---", path.display());
 
-            // Parse and analyze the file
-            match parse_rust_file(path) {
+            let result = match result {
                 Ok(ast) => {
                     processed_files += 1;
-
-                    // Display AST according to output format
-                    match format {
-                        OutputFormat::Text => {
-                            println!("AST for Rust code in {}:", path.display());
-                            let mut visitor = TextVisitor::new();
-                            visitor.visit_file(&ast);
-                        }
-                        OutputFormat::Json => {
-                            let mut visitor = JsonVisitor::new();
-                            visitor.visit_file(&ast);
-                            println!("{}", visitor.to_json());
-                        }
-                    }
+                    FileResultJson::Ast(ast)
                 }
                 Err(e) => {
                     eprintln!("Error parsing file {}: {}", path.display(), e);
+                    FileResultJson::Error(e)
+                }
+            };
+
+            if aggregate {
+                files.insert(path.display().to_string(), result);
+                continue;
+            }
+
+            // Only NDJSON records parse errors, so no output file is created for them otherwise
+            if matches!(result, FileResultJson::Error(_)) && !matches!(format, OutputFormat::Ndjson)
+            {
+                continue;
+            }
+
+            // Display AST according to output format; NDJSON also records parse errors
+            let mut file_out = match out_dir {
+                Some(out_dir) => Some(open_output(Some(&mirrored_path(
+                    directory, path, out_dir, format,
+                )))?),
+                None => None,
+            };
+            let out: &mut dyn Write = match &mut file_out {
+                Some(file_out) => file_out,
+                None => &mut *out,
+            };
+            match (result, format) {
+                (FileResultJson::Ast(ast), format) => {
+                    write_ast(&ast, Some(path), format, spans, out)?
+                }
+                (result @ FileResultJson::Error(_), OutputFormat::Ndjson) => {
+                    let entry = FileEntryJson {
+                        path: path.display().to_string(),
+                        result,
+                    };
+                    writeln!(out, "{}", serde_json::to_string(&entry)?)?;
                 }
+                (FileResultJson::Error(_), _) => {}
             }
         }
     }
 
+    if aggregate {
+        writeln!(out, "{}", serde_json::to_string_pretty(&files)?)?;
+        out.flush()?;
+    }
+
     if processed_files == 0 {
-        println!("No Rust files found in the specified directory.");
+        eprintln!("No Rust files found in the specified directory.");
     } else {
-        println!("\nProcessed {} Rust files.", processed_files);
+        eprintln!("\nProcessed {} Rust files.", processed_files);
     }
 
     Ok(())
@@ -112,36 +495,94 @@ fn process_directory(directory: &Path, format: &OutputFormat, recursive: bool) -
 fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
-    // If directory is specified, process it
+    if cli.aggregate && !matches!(cli.format, OutputFormat::Json) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--aggregate writes a JSON document and needs --format json",
+        ));
+    }
+
+    let mut out = open_output(cli.output.as_deref())?;
+
+    // If directory is specified, process it on a pool of `--jobs` threads
     if let Some(directory) = cli.directory {
-        process_directory(&directory, &cli.format, cli.recursive)?;
-        return Ok(());
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(cli.jobs.unwrap_or(0))
+            .build_global()
+            .map_err(io::Error::other)?;
+
+        return process_directory(
+            &directory,
+            &cli.format,
+            &WalkOptions {
+                max_depth: cli.max_depth.or(if cli.recursive { None } else { Some(1) }),
+                include: cli.include,
+                exclude: cli.exclude,
+                no_ignore: cli.no_ignore,
+                follow_links: cli.follow_links,
+            },
+            cli.spans,
+            cli.out_dir.as_deref(),
+            cli.aggregate,
+            &mut out,
+        );
+    }
+
+    // Read a model back from JSON, which skips parsing altogether
+    if let Some(json_path) = cli.from_json {
+        let json = read_input(&json_path)?;
+        let ast: AstJson = serde_json::from_str(&json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        return write_ast(&ast, None, &cli.format, cli.spans, &mut out);
     }
 
-    // Parse AST from file or code string (original functionality)
-    let ast = if let Some(file_path) = cli.file {
-        parse_rust_file(file_path)?
+    // Read the source from stdin, a file or the code string
+    let source = if cli.stdin {
+        read_input(Path::new("-"))?
+    } else if let Some(file_path) = cli.file {
+        read_input(&file_path)?
     } else if let Some(code) = cli.code {
-        parse_rust_source(&code).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        code
     } else {
         unreachable!("clap should require one of the arguments");
     };
 
-    // Display AST according to output format
-    match cli.format {
-        OutputFormat::Text => {
-            println!("AST for Rust code:");
-            let mut visitor = TextVisitor::new();
-            visitor.visit_file(&ast);
-        }
-        OutputFormat::Json => {
-            let mut visitor = JsonVisitor::new();
-            visitor.visit_file(&ast);
-            println!("{}", visitor.to_json());
+    // Parse a fragment such as a single expression
+    if let Some(kind) = cli.kind {
+        let fragment = parse_rust_fragment(&source, kind.into())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        match cli.format {
+            OutputFormat::Text => {
+                writeln!(out, "AST for Rust code:")?;
+                let mut visitor = TextVisitor::with_writer(&mut out).with_spans(cli.spans);
+                visitor.render_fragment(&fragment)?;
+            }
+            OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(&fragment)?)?,
+            OutputFormat::Ndjson => writeln!(out, "{}", serde_json::to_string(&fragment)?)?,
+            OutputFormat::Rust => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Rust output needs a whole file, not a fragment",
+                ));
+            }
         }
+        return out.flush();
     }
 
-    Ok(())
+    // Parse AST from the whole source (original functionality)
+    let ast =
+        parse_rust_source(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // Display AST according to output format
+    write_ast(
+        &AstJson::from_file(&ast),
+        None,
+        &cli.format,
+        cli.spans,
+        &mut out,
+    )
 }
 
 #[cfg(test)]
@@ -163,7 +604,19 @@ mod tests {
             .unwrap();
 
         // Test non-recursive directory processing
-        process_directory(temp_dir.path(), &OutputFormat::Text, false).unwrap();
+        process_directory(
+            temp_dir.path(),
+            &OutputFormat::Text,
+            &WalkOptions {
+                max_depth: Some(1),
+                ..Default::default()
+            },
+            false,
+            None,
+            false,
+            &mut io::sink(),
+        )
+        .unwrap();
 
         // Test recursive directory processing
         let nested_dir = temp_dir.path().join("nested");
@@ -174,6 +627,229 @@ mod tests {
             .write_all(b"fn nested_test() { return 42; }")
             .unwrap();
 
-        process_directory(temp_dir.path(), &OutputFormat::Text, true).unwrap();
+        process_directory(
+            temp_dir.path(),
+            &OutputFormat::Text,
+            &WalkOptions::default(),
+            true,
+            None,
+            false,
+            &mut io::sink(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_out_dir_mirrors_source_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(src.join("a")).unwrap();
+        fs::write(src.join("lib.rs"), "mod a;").unwrap();
+        fs::write(src.join("a").join("b.rs"), "fn b() {}").unwrap();
+        fs::write(src.join("bad.rs"), "fn bad( {").unwrap();
+
+        let out_dir = temp_dir.path().join("out");
+        process_directory(
+            &src,
+            &OutputFormat::Json,
+            &WalkOptions::default(),
+            false,
+            Some(&out_dir),
+            false,
+            &mut io::sink(),
+        )
+        .unwrap();
+
+        // every output file is a complete JSON document of its own
+        let json = fs::read_to_string(out_dir.join("a").join("b.rs.json")).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["items"][0]["name"], "b");
+        assert!(out_dir.join("lib.rs.json").is_file());
+
+        // a file that fails to parse leaves no empty output behind
+        assert!(!out_dir.join("bad.rs.json").exists());
+
+        assert_eq!(
+            mirrored_path(&src, &src.join("lib.rs"), &out_dir, &OutputFormat::Rust),
+            out_dir.join("lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_stdin_arguments() {
+        // `-f -` and `--stdin` both select stdin as the input
+        let cli = Cli::try_parse_from(["rusty-ast", "-f", "-", "-o", "json"]).unwrap();
+        assert_eq!(cli.file.as_deref(), Some(Path::new("-")));
+
+        let cli = Cli::try_parse_from(["rusty-ast", "--stdin", "--kind", "expr"]).unwrap();
+        assert!(cli.stdin);
+
+        // stdin is one of the mutually exclusive inputs
+        assert!(Cli::try_parse_from(["rusty-ast", "--stdin", "-c", "fn f() {}"]).is_err());
+
+        // a regular path is read as a file
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("input.rs");
+        fs::write(&file_path, "fn f() {}").unwrap();
+        assert_eq!(read_input(&file_path).unwrap(), "fn f() {}");
+    }
+
+    #[test]
+    fn test_aggregated_and_ndjson_output() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("good.rs"), "fn good() {}").unwrap();
+        fs::write(temp_dir.path().join("bad.rs"), "fn bad( {").unwrap();
+
+        // one document keyed by path, with the parse error recorded inline
+        let mut out = Vec::new();
+        process_directory(
+            temp_dir.path(),
+            &OutputFormat::Json,
+            &WalkOptions {
+                max_depth: Some(1),
+                ..Default::default()
+            },
+            false,
+            None,
+            true,
+            &mut out,
+        )
+        .unwrap();
+        let files: BTreeMap<String, FileResultJson> = serde_json::from_slice(&out).unwrap();
+        let good = temp_dir.path().join("good.rs").display().to_string();
+        let bad = temp_dir.path().join("bad.rs").display().to_string();
+        assert!(matches!(&files[&good], FileResultJson::Ast(ast) if ast.items.len() == 1));
+        assert!(matches!(files[&bad], FileResultJson::Error(_)));
+
+        // one compact object per line
+        let mut out = Vec::new();
+        process_directory(
+            temp_dir.path(),
+            &OutputFormat::Ndjson,
+            &WalkOptions {
+                max_depth: Some(1),
+                ..Default::default()
+            },
+            false,
+            None,
+            false,
+            &mut out,
+        )
+        .unwrap();
+        let entries: Vec<FileEntryJson> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        let bad_entry = entries.iter().find(|entry| entry.path == bad).unwrap();
+        assert!(matches!(bad_entry.result, FileResultJson::Error(_)));
+    }
+
+    #[test]
+    fn test_walk_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src").join("deep")).unwrap();
+        fs::create_dir_all(root.join("target").join("debug")).unwrap();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join(".gitignore"), "generated.rs\n").unwrap();
+        fs::write(root.join("build.rs"), "").unwrap();
+        fs::write(root.join("generated.rs"), "").unwrap();
+        fs::write(root.join("src").join("lib.rs"), "").unwrap();
+        fs::write(root.join("src").join("deep").join("mod.rs"), "").unwrap();
+        fs::write(root.join("target").join("CACHEDIR.TAG"), "").unwrap();
+        fs::write(root.join("target").join("debug").join("out.rs"), "").unwrap();
+        fs::write(root.join("vendor").join("dep.rs"), "").unwrap();
+
+        let found = |walk: &WalkOptions| {
+            let mut files: Vec<String> = rust_files(root, walk)
+                .unwrap()
+                .iter()
+                .map(|path| path.strip_prefix(root).unwrap().display().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+
+        // .gitignore and Cargo target directories are skipped by default
+        assert_eq!(
+            found(&WalkOptions::default()),
+            vec!["build.rs", "src/deep/mod.rs", "src/lib.rs", "vendor/dep.rs"]
+        );
+
+        // --no-ignore walks everything
+        let walk = WalkOptions {
+            no_ignore: true,
+            ..Default::default()
+        };
+        assert_eq!(found(&walk).len(), 6);
+
+        // include and exclude globs, relative to the directory
+        let walk = WalkOptions {
+            include: vec!["src/**".to_string(), "vendor/*.rs".to_string()],
+            exclude: vec!["vendor".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(found(&walk), vec!["src/deep/mod.rs", "src/lib.rs"]);
+
+        // max depth 2 reaches src/lib.rs but not src/deep/mod.rs
+        let walk = WalkOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            found(&walk),
+            vec!["build.rs", "src/lib.rs", "vendor/dep.rs"]
+        );
+    }
+
+    #[test]
+    fn test_parallel_output_is_sorted() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for i in 0..20 {
+            let dir = root.join(format!("m{}", i % 3));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join(format!("f{:02}.rs", i)),
+                format!("fn f{}() {{}}", i),
+            )
+            .unwrap();
+        }
+
+        let run = |jobs: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .unwrap();
+            let mut buffer = Vec::new();
+            pool.install(|| {
+                process_directory(
+                    root,
+                    &OutputFormat::Ndjson,
+                    &WalkOptions::default(),
+                    false,
+                    None,
+                    false,
+                    &mut buffer,
+                )
+            })
+            .unwrap();
+            String::from_utf8(buffer).unwrap()
+        };
+
+        // The same output for any number of jobs, one line per file in path order
+        let output = run(4);
+        assert_eq!(output, run(1));
+        let paths: Vec<String> = output
+            .lines()
+            .map(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["path"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(paths.len(), 20);
+        assert!(paths.is_sorted());
     }
 }
@@ -0,0 +1,1460 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Block, Expr, Fields, File, ForeignItem, GenericArgument, GenericParam, Generics,
+    Ident, ImplItem, Item, ItemFn, Label, Lit, Macro, Member, Pat, PathArguments, PathSegment,
+    RangeLimits, ReturnType, Signature, StaticMutability, Stmt, TraitItem, Type, UseTree,
+    Visibility, WherePredicate,
+};
+
+use crate::attribute::{
+    build_attributes, expr_attributes, expr_without_outer_attributes, foreign_item_attributes,
+    impl_item_attributes, item_attributes, item_without_outer_attributes, retain_inner,
+    trait_item_attributes,
+};
+use crate::model::{
+    AstJson, ClosureParamJson, ExprJson, FieldJson, FieldPatternJson, FieldValueJson, FieldsKind,
+    GenericArgumentJson, GenericParamJson, GenericsJson, ItemJson, MacroDelimiter, MacroRuleJson,
+    MatchArmJson, Node, OperatorCategory, ParameterJson, PathSegmentJson, PatternJson,
+    PositionJson, SpanJson, StmtJson, TypeJson, UsePathJson, VariantJson, VisibilityJson,
+    WherePredicateJson,
+};
+use crate::operator::{binary_operator, unary_operator};
+use crate::pretty::pretty_tokens;
+
+impl AstJson {
+    /// from_file
+    ///
+    /// # Arguments
+    /// * `file`: &File - the parsed source file
+    ///
+    /// # Returns
+    /// * `AstJson` - the model of every item in the file
+    pub fn from_file(file: &File) -> Self {
+        let (attributes, docs) = build_attributes(&file.attrs);
+        AstJson {
+            attributes,
+            docs,
+            items: file.items.iter().map(build_item).collect(),
+        }
+    }
+}
+
+/// build_span
+///
+/// # Arguments
+/// * `span`: Span - the span of a parsed syntax node
+///
+/// # Returns
+/// * `Option<SpanJson>` - the source range, or None when the span carries no location
+pub(crate) fn build_span(span: Span) -> Option<SpanJson> {
+    let start = span.start();
+    let end = span.end();
+    if start.line == 0 {
+        return None;
+    }
+
+    let bytes = span.byte_range();
+    Some(SpanJson {
+        start: PositionJson {
+            line: start.line,
+            column: start.column,
+            offset: bytes.start,
+        },
+        end: PositionJson {
+            line: end.line,
+            column: end.column,
+            offset: bytes.end,
+        },
+    })
+}
+
+/// node
+///
+/// # Arguments
+/// * `kind`: T - the model of a syntax node
+/// * `syntax`: &S - the syntax node the model was built from
+///
+/// # Returns
+/// * `Node<T>` - the model together with the location of the syntax node
+fn node<T, S: Spanned>(kind: T, syntax: &S) -> Node<T> {
+    attributed_node(kind, syntax, &[])
+}
+
+/// attributed_node
+///
+/// # Arguments
+/// * `kind`: T - the model of a syntax node
+/// * `syntax`: &S - the syntax node the model was built from
+/// * `attrs`: &[Attribute] - the attributes written on the syntax node
+///
+/// # Returns
+/// * `Node<T>` - the model together with the attributes, doc comments and location of the syntax node
+fn attributed_node<T, S: Spanned>(kind: T, syntax: &S, attrs: &[Attribute]) -> Node<T> {
+    let (attributes, docs) = build_attributes(attrs);
+    Node {
+        kind,
+        attributes,
+        docs,
+        span: build_span(syntax.span()),
+    }
+}
+
+/// build_item
+///
+/// # Arguments
+/// * `item`: &Item - the item to convert
+///
+/// # Returns
+/// * `Node<ItemJson>` - the model of the item
+pub(crate) fn build_item(item: &Item) -> Node<ItemJson> {
+    let kind = match item {
+        Item::Fn(item_fn) => return build_item_fn(item_fn),
+        Item::Struct(item_struct) => ItemJson::Struct {
+            name: item_struct.ident.to_string(),
+            visibility: build_visibility(&item_struct.vis),
+            generics: build_generics(&item_struct.generics),
+            kind: fields_kind(&item_struct.fields),
+            fields: build_fields(&item_struct.fields),
+        },
+        Item::Enum(item_enum) => {
+            let mut variants = Vec::new();
+            for variant in &item_enum.variants {
+                let (attributes, docs) = build_attributes(&variant.attrs);
+                variants.push(VariantJson {
+                    name: variant.ident.to_string(),
+                    kind: fields_kind(&variant.fields),
+                    fields: build_fields(&variant.fields),
+                    discriminant: variant
+                        .discriminant
+                        .as_ref()
+                        .map(|(_, expr)| build_expr(expr)),
+                    attributes,
+                    docs,
+                    span: build_span(variant.span()),
+                });
+            }
+
+            ItemJson::Enum {
+                name: item_enum.ident.to_string(),
+                visibility: build_visibility(&item_enum.vis),
+                generics: build_generics(&item_enum.generics),
+                variants,
+            }
+        }
+        Item::Union(item_union) => ItemJson::Union {
+            name: item_union.ident.to_string(),
+            visibility: build_visibility(&item_union.vis),
+            generics: build_generics(&item_union.generics),
+            fields: build_fields(&Fields::Named(item_union.fields.clone())),
+        },
+        Item::Impl(item_impl) => ItemJson::Impl {
+            generics: build_generics(&item_impl.generics),
+            is_default: item_impl.defaultness.is_some(),
+            is_unsafe: item_impl.unsafety.is_some(),
+            negative: matches!(item_impl.trait_, Some((Some(_), _, _))),
+            self_type: pretty_tokens(&item_impl.self_ty),
+            self_type_tree: build_type(&item_impl.self_ty),
+            trait_name: item_impl
+                .trait_
+                .as_ref()
+                .map(|(_, path, _)| pretty_tokens(path)),
+            items: item_impl.items.iter().map(build_impl_item).collect(),
+        },
+        Item::Trait(item_trait) => ItemJson::Trait {
+            name: item_trait.ident.to_string(),
+            visibility: build_visibility(&item_trait.vis),
+            generics: build_generics(&item_trait.generics),
+            is_unsafe: item_trait.unsafety.is_some(),
+            is_auto: item_trait.auto_token.is_some(),
+            supertraits: build_bounds(&item_trait.supertraits),
+            items: item_trait.items.iter().map(build_trait_item).collect(),
+        },
+        Item::Mod(item_mod) => ItemJson::Module {
+            name: item_mod.ident.to_string(),
+            visibility: build_visibility(&item_mod.vis),
+            is_unsafe: item_mod.unsafety.is_some(),
+            inline: item_mod.content.is_some(),
+            items: item_mod
+                .content
+                .as_ref()
+                .map(|(_, items)| items.iter().map(build_item).collect()),
+        },
+        Item::Use(item_use) => {
+            // an empty first segment makes the joined paths start with `::`
+            let mut prefix = Vec::new();
+            if item_use.leading_colon.is_some() {
+                prefix.push(String::new());
+            }
+
+            let mut paths = Vec::new();
+            flatten_use_tree(&item_use.tree, &mut prefix, &mut paths);
+
+            ItemJson::Use {
+                visibility: build_visibility(&item_use.vis),
+                leading_colon: item_use.leading_colon.is_some(),
+                tree: pretty_tokens(&item_use.tree),
+                paths,
+            }
+        }
+        Item::ExternCrate(item_extern_crate) => ItemJson::ExternCrate {
+            name: item_extern_crate.ident.to_string(),
+            visibility: build_visibility(&item_extern_crate.vis),
+            alias: item_extern_crate
+                .rename
+                .as_ref()
+                .map(|(_, alias)| alias.to_string()),
+        },
+        Item::Const(item_const) => ItemJson::Const {
+            name: item_const.ident.to_string(),
+            visibility: build_visibility(&item_const.vis),
+            type_info: pretty_tokens(&item_const.ty),
+            type_tree: build_type(&item_const.ty),
+            value: Some(Box::new(build_expr(&item_const.expr))),
+        },
+        Item::Static(item_static) => ItemJson::Static {
+            name: item_static.ident.to_string(),
+            visibility: build_visibility(&item_static.vis),
+            mutable: matches!(item_static.mutability, StaticMutability::Mut(_)),
+            type_info: pretty_tokens(&item_static.ty),
+            type_tree: build_type(&item_static.ty),
+            value: Some(Box::new(build_expr(&item_static.expr))),
+        },
+        Item::Type(item_type) => ItemJson::TypeAlias {
+            name: item_type.ident.to_string(),
+            visibility: build_visibility(&item_type.vis),
+            generics: build_generics(&item_type.generics),
+            bounds: Vec::new(),
+            type_info: Some(pretty_tokens(&item_type.ty)),
+            type_tree: Some(build_type(&item_type.ty)),
+        },
+        Item::Macro(item_macro) => build_macro(item_macro.ident.as_ref(), &item_macro.mac),
+        Item::ForeignMod(item_foreign_mod) => ItemJson::ExternBlock {
+            is_unsafe: item_foreign_mod.unsafety.is_some(),
+            abi: item_foreign_mod.abi.name.as_ref().map(|name| name.value()),
+            items: item_foreign_mod
+                .items
+                .iter()
+                .map(build_foreign_item)
+                .collect(),
+        },
+        // the outer attributes are kept in `attributes`, so the description leaves them out
+        _ => ItemJson::Other {
+            description: format!("{}", item_without_outer_attributes(item).to_token_stream()),
+        },
+    };
+
+    attributed_node(kind, item, item_attributes(item))
+}
+
+/// build_impl_item
+///
+/// # Arguments
+/// * `item`: &ImplItem - the associated item of an impl block
+///
+/// # Returns
+/// * `Node<ItemJson>` - the model of the associated item
+fn build_impl_item(item: &ImplItem) -> Node<ItemJson> {
+    let kind = match item {
+        ImplItem::Fn(impl_fn) => build_function(&impl_fn.vis, &impl_fn.sig, Some(&impl_fn.block)),
+        ImplItem::Const(impl_const) => ItemJson::Const {
+            name: impl_const.ident.to_string(),
+            visibility: build_visibility(&impl_const.vis),
+            type_info: pretty_tokens(&impl_const.ty),
+            type_tree: build_type(&impl_const.ty),
+            value: Some(Box::new(build_expr(&impl_const.expr))),
+        },
+        ImplItem::Type(impl_type) => ItemJson::TypeAlias {
+            name: impl_type.ident.to_string(),
+            visibility: build_visibility(&impl_type.vis),
+            generics: build_generics(&impl_type.generics),
+            bounds: Vec::new(),
+            type_info: Some(pretty_tokens(&impl_type.ty)),
+            type_tree: Some(build_type(&impl_type.ty)),
+        },
+        ImplItem::Macro(impl_macro) => build_macro(None, &impl_macro.mac),
+        _ => ItemJson::Other {
+            description: format!("{}", item.to_token_stream()),
+        },
+    };
+
+    attributed_node(kind, item, impl_item_attributes(item))
+}
+
+/// build_trait_item
+///
+/// # Arguments
+/// * `item`: &TraitItem - the associated item of a trait
+///
+/// # Returns
+/// * `Node<ItemJson>` - the model of the associated item
+fn build_trait_item(item: &TraitItem) -> Node<ItemJson> {
+    let kind = match item {
+        TraitItem::Fn(trait_fn) => build_function(
+            &Visibility::Inherited,
+            &trait_fn.sig,
+            trait_fn.default.as_ref(),
+        ),
+        TraitItem::Const(trait_const) => ItemJson::Const {
+            name: trait_const.ident.to_string(),
+            visibility: None,
+            type_info: pretty_tokens(&trait_const.ty),
+            type_tree: build_type(&trait_const.ty),
+            value: trait_const
+                .default
+                .as_ref()
+                .map(|(_, expr)| Box::new(build_expr(expr))),
+        },
+        TraitItem::Type(trait_type) => ItemJson::TypeAlias {
+            name: trait_type.ident.to_string(),
+            visibility: None,
+            generics: build_generics(&trait_type.generics),
+            bounds: build_bounds(&trait_type.bounds),
+            type_info: trait_type.default.as_ref().map(|(_, ty)| pretty_tokens(ty)),
+            type_tree: trait_type.default.as_ref().map(|(_, ty)| build_type(ty)),
+        },
+        TraitItem::Macro(trait_macro) => build_macro(None, &trait_macro.mac),
+        _ => ItemJson::Other {
+            description: format!("{}", item.to_token_stream()),
+        },
+    };
+
+    attributed_node(kind, item, trait_item_attributes(item))
+}
+
+/// build_foreign_item
+///
+/// # Arguments
+/// * `item`: &ForeignItem - an item declared inside an `extern` block
+///
+/// # Returns
+/// * `Node<ItemJson>` - the model of the foreign item
+fn build_foreign_item(item: &ForeignItem) -> Node<ItemJson> {
+    let kind = match item {
+        ForeignItem::Fn(foreign_fn) => build_function(&foreign_fn.vis, &foreign_fn.sig, None),
+        ForeignItem::Static(foreign_static) => ItemJson::Static {
+            name: foreign_static.ident.to_string(),
+            visibility: build_visibility(&foreign_static.vis),
+            mutable: matches!(foreign_static.mutability, StaticMutability::Mut(_)),
+            type_info: pretty_tokens(&foreign_static.ty),
+            type_tree: build_type(&foreign_static.ty),
+            value: None,
+        },
+        ForeignItem::Type(foreign_type) => ItemJson::TypeAlias {
+            name: foreign_type.ident.to_string(),
+            visibility: build_visibility(&foreign_type.vis),
+            generics: build_generics(&foreign_type.generics),
+            bounds: Vec::new(),
+            type_info: None,
+            type_tree: None,
+        },
+        ForeignItem::Macro(foreign_macro) => build_macro(None, &foreign_macro.mac),
+        _ => ItemJson::Other {
+            description: format!("{}", item.to_token_stream()),
+        },
+    };
+
+    attributed_node(kind, item, foreign_item_attributes(item))
+}
+
+/// build_macro
+///
+/// # Arguments
+/// * `name`: Option<&Ident> - the name defined by the macro, as in `macro_rules! name`
+/// * `mac`: &Macro - the macro invocation
+///
+/// # Returns
+/// * `ItemJson` - the model of the macro item
+fn build_macro(name: Option<&Ident>, mac: &Macro) -> ItemJson {
+    if let Some(name) = name
+        && mac.path.is_ident("macro_rules")
+        && let Some(rules) = build_macro_rules(&mac.tokens)
+    {
+        return ItemJson::MacroRules {
+            name: name.to_string(),
+            rules,
+        };
+    }
+
+    ItemJson::Macro {
+        name: name.map(|ident| ident.to_string()),
+        path: pretty_tokens(&mac.path),
+        delimiter: match mac.delimiter {
+            syn::MacroDelimiter::Paren(_) => MacroDelimiter::Paren,
+            syn::MacroDelimiter::Bracket(_) => MacroDelimiter::Bracket,
+            syn::MacroDelimiter::Brace(_) => MacroDelimiter::Brace,
+        },
+        tokens: format!("{}", mac.tokens),
+    }
+}
+
+/// build_macro_rules
+///
+/// # Arguments
+/// * `tokens`: &TokenStream - the body of a `macro_rules!` definition
+///
+/// # Returns
+/// * `Option<Vec<MacroRuleJson>>` - every `matcher => transcriber` arm, or None if the body is malformed
+fn build_macro_rules(tokens: &TokenStream) -> Option<Vec<MacroRuleJson>> {
+    let mut rules = Vec::new();
+    let mut tokens = tokens.clone().into_iter().peekable();
+
+    while let Some(matcher) = tokens.next() {
+        let TokenTree::Group(matcher) = matcher else {
+            return None;
+        };
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt)))
+                if eq.as_char() == '=' && gt.as_char() == '>' => {}
+            _ => return None,
+        }
+        let Some(TokenTree::Group(transcriber)) = tokens.next() else {
+            return None;
+        };
+
+        rules.push(MacroRuleJson {
+            matcher: matcher.to_string(),
+            transcriber: transcriber.to_string(),
+        });
+
+        if let Some(TokenTree::Punct(semi)) = tokens.peek()
+            && semi.as_char() == ';'
+        {
+            tokens.next();
+        }
+    }
+
+    Some(rules)
+}
+
+/// flatten_use_tree
+///
+/// # Arguments
+/// * `tree`: &UseTree - the use tree to flatten
+/// * `prefix`: &mut Vec<String> - the path segments leading to `tree`
+/// * `paths`: &mut Vec<UsePathJson> - receives one entry per imported path
+///
+/// # Returns
+/// * `()`
+fn flatten_use_tree(tree: &UseTree, prefix: &mut Vec<String>, paths: &mut Vec<UsePathJson>) {
+    match tree {
+        UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            flatten_use_tree(&use_path.tree, prefix, paths);
+            prefix.pop();
+        }
+        UseTree::Name(use_name) => paths.push(UsePathJson {
+            path: use_tree_path(prefix, &use_name.ident),
+            alias: None,
+            glob: false,
+        }),
+        UseTree::Rename(use_rename) => paths.push(UsePathJson {
+            path: use_tree_path(prefix, &use_rename.ident),
+            alias: Some(use_rename.rename.to_string()),
+            glob: false,
+        }),
+        UseTree::Glob(_) => paths.push(UsePathJson {
+            path: prefix.join("::"),
+            alias: None,
+            glob: true,
+        }),
+        UseTree::Group(use_group) => {
+            for item in &use_group.items {
+                flatten_use_tree(item, prefix, paths);
+            }
+        }
+    }
+}
+
+/// use_tree_path
+///
+/// # Arguments
+/// * `prefix`: &[String] - the path segments leading to the imported name
+/// * `ident`: &Ident - the imported name, where `self` stands for the prefix itself
+///
+/// # Returns
+/// * `String` - the full path joined with `::`
+fn use_tree_path(prefix: &[String], ident: &Ident) -> String {
+    if ident == "self" && prefix.iter().any(|segment| !segment.is_empty()) {
+        return prefix.join("::");
+    }
+
+    let mut segments = prefix.to_vec();
+    segments.push(ident.to_string());
+    segments.join("::")
+}
+
+/// build_visibility
+///
+/// # Arguments
+/// * `vis`: &Visibility - the visibility of an item or field
+///
+/// # Returns
+/// * `Option<VisibilityJson>` - the visibility, or None when the item or field is private
+fn build_visibility(vis: &Visibility) -> Option<VisibilityJson> {
+    match vis {
+        Visibility::Public(_) => Some(VisibilityJson::Public),
+        Visibility::Restricted(restricted)
+            if restricted.in_token.is_none() && restricted.path.is_ident("crate") =>
+        {
+            Some(VisibilityJson::Crate)
+        }
+        Visibility::Restricted(restricted) => Some(VisibilityJson::Restricted {
+            path: pretty_tokens(&restricted.path),
+        }),
+        Visibility::Inherited => None,
+    }
+}
+
+/// build_generics
+///
+/// # Arguments
+/// * `generics`: &Generics - the generic parameters and where clause of an item
+///
+/// # Returns
+/// * `GenericsJson` - the model of the generics
+fn build_generics(generics: &Generics) -> GenericsJson {
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(type_param) => GenericParamJson::Type {
+                name: type_param.ident.to_string(),
+                bounds: build_bounds(&type_param.bounds),
+                default: type_param.default.as_ref().map(pretty_tokens),
+            },
+            GenericParam::Lifetime(lifetime_param) => GenericParamJson::Lifetime {
+                name: lifetime_param.lifetime.to_string(),
+                bounds: build_bounds(&lifetime_param.bounds),
+            },
+            GenericParam::Const(const_param) => GenericParamJson::Const {
+                name: const_param.ident.to_string(),
+                type_info: pretty_tokens(&const_param.ty),
+                default: const_param.default.as_ref().map(pretty_tokens),
+            },
+        })
+        .collect();
+
+    let mut where_clause = Vec::new();
+    for predicate in generics.where_clause.iter().flat_map(|w| &w.predicates) {
+        match predicate {
+            WherePredicate::Type(predicate_type) => {
+                where_clause.push(WherePredicateJson::Type {
+                    lifetimes: predicate_type
+                        .lifetimes
+                        .iter()
+                        .flat_map(|bound| &bound.lifetimes)
+                        .map(pretty_tokens)
+                        .collect(),
+                    bounded_type: pretty_tokens(&predicate_type.bounded_ty),
+                    bounds: build_bounds(&predicate_type.bounds),
+                });
+            }
+            WherePredicate::Lifetime(predicate_lifetime) => {
+                where_clause.push(WherePredicateJson::Lifetime {
+                    lifetime: predicate_lifetime.lifetime.to_string(),
+                    bounds: build_bounds(&predicate_lifetime.bounds),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    GenericsJson {
+        params,
+        where_clause,
+    }
+}
+
+/// build_bounds
+///
+/// # Arguments
+/// * `bounds`: &Punctuated<T, P> - the `+`-separated bounds of a parameter or predicate
+///
+/// # Returns
+/// * `Vec<String>` - every bound as source text
+fn build_bounds<T: ToTokens, P>(bounds: &Punctuated<T, P>) -> Vec<String> {
+    bounds.iter().map(pretty_tokens).collect()
+}
+
+/// build_fields
+///
+/// # Arguments
+/// * `fields`: &Fields - the fields of a struct, union or enum variant
+///
+/// # Returns
+/// * `Vec<FieldJson>` - the model of every field
+fn build_fields(fields: &Fields) -> Vec<FieldJson> {
+    let mut result = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let (attributes, docs) = build_attributes(&field.attrs);
+        result.push(FieldJson {
+            name: field.ident.as_ref().map(|ident| ident.to_string()),
+            index: field.ident.is_none().then_some(index),
+            visibility: build_visibility(&field.vis),
+            type_info: pretty_tokens(&field.ty),
+            type_tree: build_type(&field.ty),
+            attributes,
+            docs,
+            span: build_span(field.span()),
+        });
+    }
+    result
+}
+
+/// fields_kind
+///
+/// # Arguments
+/// * `fields`: &Fields - the fields of a struct or enum variant
+///
+/// # Returns
+/// * `FieldsKind` - whether the fields are named, positional or absent
+fn fields_kind(fields: &Fields) -> FieldsKind {
+    match fields {
+        Fields::Named(_) => FieldsKind::Named,
+        Fields::Unnamed(_) => FieldsKind::Tuple,
+        Fields::Unit => FieldsKind::Unit,
+    }
+}
+
+/// build_item_fn
+///
+/// # Arguments
+/// * `item_fn`: &ItemFn - the function to convert
+///
+/// # Returns
+/// * `Node<ItemJson>` - the model of the function
+pub(crate) fn build_item_fn(item_fn: &ItemFn) -> Node<ItemJson> {
+    attributed_node(
+        build_function(&item_fn.vis, &item_fn.sig, Some(&item_fn.block)),
+        item_fn,
+        &item_fn.attrs,
+    )
+}
+
+/// build_function
+///
+/// # Arguments
+/// * `vis`: &Visibility - the visibility of the function
+/// * `sig`: &Signature - the signature of the function
+/// * `block`: Option<&Block> - the body, or None for a declaration without one
+///
+/// # Returns
+/// * `ItemJson` - the model of the function
+fn build_function(vis: &Visibility, sig: &Signature, block: Option<&Block>) -> ItemJson {
+    let mut parameters = Vec::new();
+    for param in &sig.inputs {
+        match param {
+            syn::FnArg::Typed(pat_type) => {
+                let name = match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                    pat => pretty_tokens(pat),
+                };
+
+                parameters.push(ParameterJson {
+                    name,
+                    pattern: Some(build_pattern(&pat_type.pat)),
+                    type_info: pretty_tokens(&pat_type.ty),
+                    type_tree: build_type(&pat_type.ty),
+                    attributes: build_attributes(&pat_type.attrs).0,
+                    span: build_span(pat_type.span()),
+                });
+            }
+            syn::FnArg::Receiver(receiver) => {
+                // the receiver is printed as written, as in `&mut self`, but without its attributes
+                let bare = syn::Receiver {
+                    attrs: Vec::new(),
+                    ..receiver.clone()
+                };
+                parameters.push(ParameterJson {
+                    name: "self".to_string(),
+                    pattern: None,
+                    type_info: pretty_tokens(&bare),
+                    type_tree: build_type(&receiver.ty),
+                    attributes: build_attributes(&receiver.attrs).0,
+                    span: build_span(receiver.span()),
+                });
+            }
+        }
+    }
+
+    ItemJson::Function {
+        name: sig.ident.to_string(),
+        visibility: build_visibility(vis),
+        is_const: sig.constness.is_some(),
+        is_async: sig.asyncness.is_some(),
+        is_unsafe: sig.unsafety.is_some(),
+        is_extern: sig.abi.is_some(),
+        abi: sig
+            .abi
+            .as_ref()
+            .and_then(|abi| abi.name.as_ref())
+            .map(|name| name.value()),
+        generics: build_generics(&sig.generics),
+        parameters,
+        variadic: sig.variadic.is_some(),
+        return_type: output_type(&sig.output).map(pretty_tokens),
+        return_type_tree: output_type(&sig.output).map(build_type),
+        body: block.map(build_block),
+    }
+}
+
+/// output_type
+///
+/// # Arguments
+/// * `output`: &ReturnType - the return type of a function, closure or function pointer
+///
+/// # Returns
+/// * `Option<&Type>` - the declared type, or None when it is left out
+fn output_type(output: &ReturnType) -> Option<&Type> {
+    match output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => Some(ty),
+    }
+}
+
+/// build_type
+///
+/// # Arguments
+/// * `ty`: &Type - the type to convert
+///
+/// # Returns
+/// * `Node<TypeJson>` - the model of the type
+pub(crate) fn build_type(ty: &Type) -> Node<TypeJson> {
+    let kind = match ty {
+        Type::Path(type_path) => TypeJson::Path {
+            qself: type_path
+                .qself
+                .as_ref()
+                .map(|qself| Box::new(build_type(&qself.ty))),
+            leading_colon: type_path.path.leading_colon.is_some(),
+            segments: type_path
+                .path
+                .segments
+                .iter()
+                .map(build_path_segment)
+                .collect(),
+        },
+        Type::Reference(type_reference) => TypeJson::Reference {
+            lifetime: type_reference
+                .lifetime
+                .as_ref()
+                .map(|lifetime| lifetime.to_string()),
+            mutable: type_reference.mutability.is_some(),
+            elem: Box::new(build_type(&type_reference.elem)),
+        },
+        Type::Ptr(type_ptr) => TypeJson::Pointer {
+            mutable: type_ptr.mutability.is_some(),
+            elem: Box::new(build_type(&type_ptr.elem)),
+        },
+        Type::Slice(type_slice) => TypeJson::Slice {
+            elem: Box::new(build_type(&type_slice.elem)),
+        },
+        Type::Array(type_array) => TypeJson::Array {
+            elem: Box::new(build_type(&type_array.elem)),
+            length: Box::new(build_expr(&type_array.len)),
+        },
+        Type::Tuple(type_tuple) => TypeJson::Tuple {
+            elements: type_tuple.elems.iter().map(build_type).collect(),
+        },
+        Type::BareFn(type_bare_fn) => TypeJson::FnPointer {
+            lifetimes: type_bare_fn
+                .lifetimes
+                .iter()
+                .flat_map(|bound| &bound.lifetimes)
+                .map(pretty_tokens)
+                .collect(),
+            is_unsafe: type_bare_fn.unsafety.is_some(),
+            abi: type_bare_fn
+                .abi
+                .as_ref()
+                .and_then(|abi| abi.name.as_ref())
+                .map(|name| name.value()),
+            inputs: type_bare_fn
+                .inputs
+                .iter()
+                .map(|input| build_type(&input.ty))
+                .collect(),
+            variadic: type_bare_fn.variadic.is_some(),
+            output: output_type(&type_bare_fn.output).map(|ty| Box::new(build_type(ty))),
+        },
+        Type::ImplTrait(type_impl_trait) => TypeJson::ImplTrait {
+            bounds: build_bounds(&type_impl_trait.bounds),
+        },
+        Type::TraitObject(type_trait_object) => TypeJson::TraitObject {
+            bounds: build_bounds(&type_trait_object.bounds),
+        },
+        Type::Never(_) => TypeJson::Never,
+        Type::Infer(_) => TypeJson::Infer,
+        // parentheses and invisible groups do not change the type they wrap
+        Type::Paren(type_paren) => return build_type(&type_paren.elem),
+        Type::Group(type_group) => return build_type(&type_group.elem),
+        _ => TypeJson::Other {
+            description: pretty_tokens(ty),
+        },
+    };
+
+    node(kind, ty)
+}
+
+/// build_path_segment
+///
+/// # Arguments
+/// * `segment`: &PathSegment - one segment of a type path
+///
+/// # Returns
+/// * `PathSegmentJson` - the model of the segment and its generic arguments
+fn build_path_segment(segment: &PathSegment) -> PathSegmentJson {
+    let mut segment_json = PathSegmentJson {
+        name: segment.ident.to_string(),
+        arguments: Vec::new(),
+        parenthesized: false,
+        inputs: Vec::new(),
+        output: None,
+    };
+
+    match &segment.arguments {
+        PathArguments::None => {}
+        PathArguments::AngleBracketed(arguments) => {
+            segment_json.arguments = arguments
+                .args
+                .iter()
+                .filter_map(build_generic_argument)
+                .collect();
+        }
+        PathArguments::Parenthesized(arguments) => {
+            segment_json.parenthesized = true;
+            segment_json.inputs = arguments.inputs.iter().map(build_type).collect();
+            segment_json.output = output_type(&arguments.output).map(|ty| Box::new(build_type(ty)));
+        }
+    }
+
+    segment_json
+}
+
+/// build_generic_argument
+///
+/// # Arguments
+/// * `argument`: &GenericArgument - an argument between the angle brackets of a path segment
+///
+/// # Returns
+/// * `Option<GenericArgumentJson>` - the model of the argument, None for a kind syn adds later
+fn build_generic_argument(argument: &GenericArgument) -> Option<GenericArgumentJson> {
+    let argument = match argument {
+        GenericArgument::Lifetime(lifetime) => GenericArgumentJson::Lifetime {
+            name: lifetime.to_string(),
+        },
+        GenericArgument::Type(ty) => GenericArgumentJson::Type {
+            value: build_type(ty),
+        },
+        GenericArgument::Const(expr) => GenericArgumentJson::Const {
+            expr: pretty_tokens(expr),
+        },
+        GenericArgument::AssocType(assoc_type) => GenericArgumentJson::AssocType {
+            name: assoc_type.ident.to_string(),
+            value: build_type(&assoc_type.ty),
+        },
+        GenericArgument::AssocConst(assoc_const) => GenericArgumentJson::AssocConst {
+            name: assoc_const.ident.to_string(),
+            expr: pretty_tokens(&assoc_const.value),
+        },
+        GenericArgument::Constraint(constraint) => GenericArgumentJson::Constraint {
+            name: constraint.ident.to_string(),
+            bounds: build_bounds(&constraint.bounds),
+        },
+        _ => return None,
+    };
+
+    Some(argument)
+}
+
+/// build_block
+///
+/// # Arguments
+/// * `block`: &Block - the block to convert
+///
+/// # Returns
+/// * `Vec<Node<StmtJson>>` - the model of every statement in the block
+fn build_block(block: &Block) -> Vec<Node<StmtJson>> {
+    block.stmts.iter().map(build_stmt).collect()
+}
+
+/// build_stmt
+///
+/// # Arguments
+/// * `stmt`: &Stmt - the statement to convert
+///
+/// # Returns
+/// * `Node<StmtJson>` - the model of the statement
+pub(crate) fn build_stmt(stmt: &Stmt) -> Node<StmtJson> {
+    let kind = match stmt {
+        Stmt::Local(local) => {
+            let (pat, ty) = match &local.pat {
+                Pat::Type(pat_type) => (&*pat_type.pat, Some(&*pat_type.ty)),
+                pat => (pat, None),
+            };
+
+            let name = match pat {
+                Pat::Ident(pat_ident) if pat_ident.subpat.is_none() => {
+                    Some(pat_ident.ident.to_string())
+                }
+                _ => None,
+            };
+
+            let initializer = local
+                .init
+                .as_ref()
+                .map(|init| Box::new(build_expr(&init.expr)));
+
+            let else_branch = local
+                .init
+                .as_ref()
+                .and_then(|init| init.diverge.as_ref())
+                .map(|(_, diverge)| Box::new(build_expr(diverge)));
+
+            StmtJson::VariableDeclaration {
+                name,
+                pattern: Box::new(build_pattern(pat)),
+                type_info: ty.map(pretty_tokens),
+                type_tree: ty.map(|ty| Box::new(build_type(ty))),
+                initializer,
+                else_branch,
+            }
+        }
+        Stmt::Expr(expr, semi) => StmtJson::Expression {
+            expr: Box::new(build_expr(expr)),
+            semicolon: semi.is_some(),
+        },
+        Stmt::Item(item) => StmtJson::Item {
+            item: Box::new(build_item(item)),
+        },
+        Stmt::Macro(stmt_macro) => {
+            let mut stmt_macro = stmt_macro.clone();
+            retain_inner(&mut stmt_macro.attrs);
+            StmtJson::Other {
+                description: format!("{}", stmt_macro.to_token_stream()),
+            }
+        }
+    };
+
+    // expressions and items carry their own attributes, so only `let` and macro statements add theirs
+    let attrs = match stmt {
+        Stmt::Local(local) => &local.attrs[..],
+        Stmt::Macro(stmt_macro) => &stmt_macro.attrs[..],
+        _ => &[],
+    };
+    attributed_node(kind, stmt, attrs)
+}
+
+/// build_expr
+///
+/// # Arguments
+/// * `expr`: &Expr - the expression to convert
+///
+/// # Returns
+/// * `Node<ExprJson>` - the model of the expression
+pub(crate) fn build_expr(expr: &Expr) -> Node<ExprJson> {
+    let kind = match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(lit_int) => ExprJson::IntLiteral {
+                value: lit_int.base10_digits().to_string(),
+                suffix: build_suffix(lit_int.suffix()),
+                raw: lit_int.token().to_string(),
+            },
+            Lit::Float(lit_float) => ExprJson::FloatLiteral {
+                value: lit_float.base10_digits().to_string(),
+                suffix: build_suffix(lit_float.suffix()),
+                raw: lit_float.token().to_string(),
+            },
+            Lit::Str(lit_str) => ExprJson::StringLiteral {
+                value: lit_str.value(),
+                raw: lit_str.token().to_string(),
+            },
+            Lit::Bool(lit_bool) => ExprJson::BoolLiteral {
+                value: lit_bool.value,
+            },
+            Lit::Char(lit_char) => ExprJson::CharLiteral {
+                value: lit_char.value(),
+                raw: lit_char.token().to_string(),
+            },
+            Lit::Byte(lit_byte) => ExprJson::ByteLiteral {
+                value: lit_byte.value(),
+                raw: lit_byte.token().to_string(),
+            },
+            Lit::ByteStr(lit_byte_str) => ExprJson::ByteStringLiteral {
+                value: lit_byte_str.value(),
+                raw: lit_byte_str.token().to_string(),
+            },
+            Lit::CStr(lit_cstr) => ExprJson::CStringLiteral {
+                value: lit_cstr.value().to_string_lossy().into_owned(),
+                raw: lit_cstr.token().to_string(),
+            },
+            _ => ExprJson::VerbatimLiteral {
+                raw: format!("{}", expr_lit.to_token_stream()),
+            },
+        },
+        Expr::Struct(expr_struct) => ExprJson::StructLiteral {
+            path: pretty_tokens(&expr_struct.path),
+            fields: expr_struct
+                .fields
+                .iter()
+                .map(|field_value| {
+                    let (attributes, docs) = build_attributes(&field_value.attrs);
+                    FieldValueJson {
+                        member: build_member(&field_value.member),
+                        value: build_expr(&field_value.expr),
+                        shorthand: field_value.colon_token.is_none(),
+                        attributes,
+                        docs,
+                        span: build_span(field_value.span()),
+                    }
+                })
+                .collect(),
+            base: expr_struct
+                .rest
+                .as_ref()
+                .map(|rest| Box::new(build_expr(rest))),
+        },
+        Expr::Tuple(expr_tuple) => ExprJson::Tuple {
+            elements: expr_tuple.elems.iter().map(build_expr).collect(),
+        },
+        Expr::Array(expr_array) => ExprJson::Array {
+            elements: expr_array.elems.iter().map(build_expr).collect(),
+        },
+        Expr::Repeat(expr_repeat) => ExprJson::ArrayRepeat {
+            value: Box::new(build_expr(&expr_repeat.expr)),
+            length: Box::new(build_expr(&expr_repeat.len)),
+        },
+        Expr::Range(expr_range) => ExprJson::Range {
+            start: expr_range.start.as_ref().map(|e| Box::new(build_expr(e))),
+            end: expr_range.end.as_ref().map(|e| Box::new(build_expr(e))),
+            inclusive: matches!(expr_range.limits, RangeLimits::Closed(_)),
+        },
+        Expr::Paren(expr_paren) => ExprJson::Parenthesized {
+            expr: Box::new(build_expr(&expr_paren.expr)),
+        },
+        // invisible groups only come from macro expansion, so the inner expression stands for them
+        Expr::Group(expr_group) => return build_expr(&expr_group.expr),
+        Expr::Binary(expr_bin) => match binary_operator(&expr_bin.op) {
+            Some((operator, OperatorCategory::Assignment)) => ExprJson::CompoundAssign {
+                operator: operator.to_string(),
+                category: OperatorCategory::Assignment,
+                target: Box::new(build_expr(&expr_bin.left)),
+                value: Box::new(build_expr(&expr_bin.right)),
+            },
+            Some((operator, category)) => ExprJson::Binary {
+                operator: operator.to_string(),
+                category,
+                left: Box::new(build_expr(&expr_bin.left)),
+                right: Box::new(build_expr(&expr_bin.right)),
+            },
+            None => ExprJson::Other {
+                description: format!("{}", expr_without_outer_attributes(expr).to_token_stream()),
+            },
+        },
+        Expr::Call(expr_call) => ExprJson::FunctionCall {
+            function: Box::new(build_expr(&expr_call.func)),
+            arguments: expr_call.args.iter().map(build_expr).collect(),
+        },
+        Expr::Path(expr_path) => {
+            // the attributes are already recorded on the node
+            let path = syn::ExprPath {
+                attrs: Vec::new(),
+                ..expr_path.clone()
+            };
+            ExprJson::Identifier {
+                name: pretty_tokens(&path),
+            }
+        }
+        Expr::If(expr_if) => ExprJson::If {
+            condition: Box::new(build_expr(&expr_if.cond)),
+            then_branch: build_block(&expr_if.then_branch),
+            else_branch: expr_if
+                .else_branch
+                .as_ref()
+                .map(|(_, else_expr)| Box::new(build_expr(else_expr))),
+        },
+        Expr::Loop(expr_loop) => ExprJson::Loop {
+            label: build_label(expr_loop.label.as_ref()),
+            body: build_block(&expr_loop.body),
+        },
+        Expr::While(expr_while) => ExprJson::While {
+            label: build_label(expr_while.label.as_ref()),
+            condition: Box::new(build_expr(&expr_while.cond)),
+            body: build_block(&expr_while.body),
+        },
+        Expr::ForLoop(expr_for) => ExprJson::ForLoop {
+            label: build_label(expr_for.label.as_ref()),
+            pattern: Box::new(build_pattern(&expr_for.pat)),
+            iterable: Box::new(build_expr(&expr_for.expr)),
+            body: build_block(&expr_for.body),
+        },
+        Expr::Break(expr_break) => ExprJson::Break {
+            label: expr_break.label.as_ref().map(|label| label.to_string()),
+            value: expr_break.expr.as_ref().map(|e| Box::new(build_expr(e))),
+        },
+        Expr::Continue(expr_continue) => ExprJson::Continue {
+            label: expr_continue.label.as_ref().map(|label| label.to_string()),
+        },
+        Expr::Return(expr_return) => ExprJson::Return {
+            value: expr_return.expr.as_ref().map(|e| Box::new(build_expr(e))),
+        },
+        Expr::Let(expr_let) => ExprJson::Let {
+            pattern: Box::new(build_pattern(&expr_let.pat)),
+            expr: Box::new(build_expr(&expr_let.expr)),
+        },
+        Expr::Try(expr_try) => ExprJson::Try {
+            expr: Box::new(build_expr(&expr_try.expr)),
+        },
+        Expr::Await(expr_await) => ExprJson::Await {
+            expr: Box::new(build_expr(&expr_await.base)),
+        },
+        Expr::Match(expr_match) => ExprJson::Match {
+            expr: Box::new(build_expr(&expr_match.expr)),
+            arms: expr_match
+                .arms
+                .iter()
+                .map(|arm| {
+                    let (attributes, docs) = build_attributes(&arm.attrs);
+                    MatchArmJson {
+                        pattern: build_pattern(&arm.pat),
+                        guard: arm
+                            .guard
+                            .as_ref()
+                            .map(|(_, guard)| Box::new(build_expr(guard))),
+                        body: Box::new(build_expr(&arm.body)),
+                        attributes,
+                        docs,
+                        span: build_span(arm.span()),
+                    }
+                })
+                .collect(),
+        },
+        Expr::Closure(expr_closure) => ExprJson::Closure {
+            lifetimes: expr_closure
+                .lifetimes
+                .iter()
+                .flat_map(|bound| &bound.lifetimes)
+                .map(pretty_tokens)
+                .collect(),
+            is_const: expr_closure.constness.is_some(),
+            is_static: expr_closure.movability.is_some(),
+            is_move: expr_closure.capture.is_some(),
+            is_async: expr_closure.asyncness.is_some(),
+            parameters: expr_closure
+                .inputs
+                .iter()
+                .map(|input| match input {
+                    Pat::Type(pat_type) => ClosureParamJson {
+                        pattern: build_pattern(&pat_type.pat),
+                        type_info: Some(pretty_tokens(&pat_type.ty)),
+                        type_tree: Some(build_type(&pat_type.ty)),
+                        span: build_span(input.span()),
+                    },
+                    _ => ClosureParamJson {
+                        pattern: build_pattern(input),
+                        type_info: None,
+                        type_tree: None,
+                        span: build_span(input.span()),
+                    },
+                })
+                .collect(),
+            return_type: output_type(&expr_closure.output).map(pretty_tokens),
+            return_type_tree: output_type(&expr_closure.output).map(build_type),
+            body: Box::new(build_expr(&expr_closure.body)),
+        },
+        Expr::MethodCall(expr_method_call) => ExprJson::MethodCall {
+            receiver: Box::new(build_expr(&expr_method_call.receiver)),
+            method: expr_method_call.method.to_string(),
+            turbofish: expr_method_call
+                .turbofish
+                .iter()
+                .flat_map(|turbofish| &turbofish.args)
+                .map(pretty_tokens)
+                .collect(),
+            arguments: expr_method_call.args.iter().map(build_expr).collect(),
+        },
+        Expr::Field(expr_field) => ExprJson::FieldAccess {
+            base: Box::new(build_expr(&expr_field.base)),
+            member: build_member(&expr_field.member),
+        },
+        Expr::Index(expr_index) => ExprJson::Index {
+            base: Box::new(build_expr(&expr_index.expr)),
+            index: Box::new(build_expr(&expr_index.index)),
+        },
+        Expr::Unary(expr_unary) => match unary_operator(&expr_unary.op) {
+            Some((operator, category)) => ExprJson::Unary {
+                operator: operator.to_string(),
+                category,
+                operand: Box::new(build_expr(&expr_unary.expr)),
+            },
+            None => ExprJson::Other {
+                description: format!("{}", expr_without_outer_attributes(expr).to_token_stream()),
+            },
+        },
+        Expr::Reference(expr_reference) => ExprJson::Reference {
+            mutable: expr_reference.mutability.is_some(),
+            expr: Box::new(build_expr(&expr_reference.expr)),
+        },
+        Expr::Cast(expr_cast) => ExprJson::Cast {
+            expr: Box::new(build_expr(&expr_cast.expr)),
+            type_info: pretty_tokens(&expr_cast.ty),
+            type_tree: build_type(&expr_cast.ty),
+        },
+        Expr::Assign(expr_assign) => ExprJson::Assign {
+            target: Box::new(build_expr(&expr_assign.left)),
+            value: Box::new(build_expr(&expr_assign.right)),
+        },
+        Expr::Block(expr_block) => ExprJson::Block {
+            label: build_label(expr_block.label.as_ref()),
+            body: build_block(&expr_block.block),
+        },
+        Expr::Async(expr_async) => ExprJson::AsyncBlock {
+            is_move: expr_async.capture.is_some(),
+            body: build_block(&expr_async.block),
+        },
+        Expr::Unsafe(expr_unsafe) => ExprJson::UnsafeBlock {
+            body: build_block(&expr_unsafe.block),
+        },
+        Expr::Const(expr_const) => ExprJson::ConstBlock {
+            body: build_block(&expr_const.block),
+        },
+        _ => ExprJson::Other {
+            description: format!("{}", expr_without_outer_attributes(expr).to_token_stream()),
+        },
+    };
+
+    attributed_node(kind, expr, expr_attributes(expr))
+}
+
+/// build_pattern
+///
+/// # Arguments
+/// * `pat`: &Pat - the pattern to convert
+///
+/// # Returns
+/// * `Node<PatternJson>` - the model of the pattern
+pub(crate) fn build_pattern(pat: &Pat) -> Node<PatternJson> {
+    let kind = match pat {
+        Pat::Ident(pat_ident) => PatternJson::Identifier {
+            name: pat_ident.ident.to_string(),
+            by_ref: pat_ident.by_ref.is_some(),
+            mutable: pat_ident.mutability.is_some(),
+            subpattern: pat_ident
+                .subpat
+                .as_ref()
+                .map(|(_, subpat)| Box::new(build_pattern(subpat))),
+        },
+        Pat::Wild(_) => PatternJson::Wildcard,
+        Pat::Rest(_) => PatternJson::Rest,
+        Pat::Lit(pat_lit) => PatternJson::Literal {
+            value: Box::new(build_expr(&Expr::Lit(pat_lit.clone()))),
+        },
+        Pat::Range(pat_range) => PatternJson::Range {
+            start: pat_range.start.as_ref().map(|e| Box::new(build_expr(e))),
+            end: pat_range.end.as_ref().map(|e| Box::new(build_expr(e))),
+            inclusive: matches!(pat_range.limits, RangeLimits::Closed(_)),
+        },
+        Pat::Path(pat_path) => PatternJson::Path {
+            path: pretty_tokens(pat_path),
+        },
+        Pat::Tuple(pat_tuple) => PatternJson::Tuple {
+            elements: pat_tuple.elems.iter().map(build_pattern).collect(),
+        },
+        Pat::TupleStruct(pat_tuple_struct) => PatternJson::TupleStruct {
+            path: pretty_tokens(&pat_tuple_struct.path),
+            elements: pat_tuple_struct.elems.iter().map(build_pattern).collect(),
+        },
+        Pat::Struct(pat_struct) => PatternJson::Struct {
+            path: pretty_tokens(&pat_struct.path),
+            fields: pat_struct
+                .fields
+                .iter()
+                .map(|field_pat| FieldPatternJson {
+                    member: build_member(&field_pat.member),
+                    pattern: build_pattern(&field_pat.pat),
+                    shorthand: field_pat.colon_token.is_none(),
+                    attributes: build_attributes(&field_pat.attrs).0,
+                    span: build_span(field_pat.span()),
+                })
+                .collect(),
+            has_rest: pat_struct.rest.is_some(),
+        },
+        Pat::Slice(pat_slice) => PatternJson::Slice {
+            elements: pat_slice.elems.iter().map(build_pattern).collect(),
+        },
+        Pat::Reference(pat_reference) => PatternJson::Reference {
+            mutable: pat_reference.mutability.is_some(),
+            pattern: Box::new(build_pattern(&pat_reference.pat)),
+        },
+        Pat::Or(pat_or) => PatternJson::Or {
+            cases: pat_or.cases.iter().map(build_pattern).collect(),
+        },
+        // parentheses only group, and a type ascription is recorded by the let or parameter that owns it
+        Pat::Paren(pat_paren) => return build_pattern(&pat_paren.pat),
+        Pat::Type(pat_type) => return build_pattern(&pat_type.pat),
+        _ => PatternJson::Other {
+            description: format!("{}", pat.to_token_stream()),
+        },
+    };
+
+    node(kind, pat)
+}
+
+/// build_suffix
+///
+/// # Arguments
+/// * `suffix`: &str - the suffix of a numeric literal, empty when there is none
+///
+/// # Returns
+/// * `Option<String>` - the suffix, or None when the literal has no suffix
+fn build_suffix(suffix: &str) -> Option<String> {
+    (!suffix.is_empty()).then(|| suffix.to_string())
+}
+
+/// build_member
+///
+/// # Arguments
+/// * `member`: &Member - a named field or a tuple index
+///
+/// # Returns
+/// * `String` - the field name or the index as text
+fn build_member(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// build_label
+///
+/// # Arguments
+/// * `label`: Option<&Label> - the label of a loop or block, if any
+///
+/// # Returns
+/// * `Option<String>` - the label name including its leading quote, as in `'outer`
+fn build_label(label: Option<&Label>) -> Option<String> {
+    label.map(|label| label.name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rust_source;
+
+    #[test]
+    fn test_from_file_builds_every_item() {
+        let source = r#"
+            struct Point {
+                x: f64,
+            }
+
+            fn origin() -> Point {
+                struct Inner;
+                Point { x: 0.0 }
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let ast = AstJson::from_file(&file);
+
+        // top-level items keep their order
+        assert_eq!(ast.items.len(), 2);
+        assert!(matches!(&ast.items[0].kind, ItemJson::Struct { name, .. } if name == "Point"));
+
+        // items nested in a function body become item statements
+        if let ItemJson::Function {
+            body: Some(body), ..
+        } = &ast.items[1].kind
+        {
+            assert!(
+                matches!(&body[0].kind, StmtJson::Item { item } if matches!(item.kind, ItemJson::Struct { .. }))
+            );
+        } else {
+            panic!("Second item is not a function");
+        }
+    }
+
+    #[test]
+    fn test_build_every_item_kind() {
+        let source = r#"
+            use std::fmt;
+            extern crate alloc;
+            const N: usize = 3;
+            static S: &str = "x";
+            type R = Result<(), ()>;
+            union U { a: u32 }
+            macro_rules! m { () => {}; }
+            mod inner { fn g() {} }
+            mod outer;
+            trait T { fn required(&self); }
+            impl T for U { fn required(&self) {} }
+            extern "C" { fn abs(x: i32) -> i32; }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let ast = AstJson::from_file(&file);
+
+        let kinds: Vec<&str> = ast
+            .items
+            .iter()
+            .map(|item| match &item.kind {
+                ItemJson::Use { .. } => "Use",
+                ItemJson::ExternCrate { .. } => "ExternCrate",
+                ItemJson::Const { .. } => "Const",
+                ItemJson::Static { .. } => "Static",
+                ItemJson::TypeAlias { .. } => "TypeAlias",
+                ItemJson::Union { .. } => "Union",
+                ItemJson::MacroRules { .. } => "MacroRules",
+                ItemJson::Module { .. } => "Module",
+                ItemJson::Trait { .. } => "Trait",
+                ItemJson::Impl { .. } => "Impl",
+                ItemJson::ExternBlock { .. } => "ExternBlock",
+                _ => "Other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "Use",
+                "ExternCrate",
+                "Const",
+                "Static",
+                "TypeAlias",
+                "Union",
+                "MacroRules",
+                "Module",
+                "Module",
+                "Trait",
+                "Impl",
+                "ExternBlock"
+            ]
+        );
+
+        // out-of-line modules have no items
+        assert!(matches!(
+            &ast.items[8].kind,
+            ItemJson::Module { items: None, .. }
+        ));
+
+        // required trait methods have no body, impl methods do
+        if let ItemJson::Trait { items, .. } = &ast.items[9].kind {
+            assert!(matches!(
+                &items[0].kind,
+                ItemJson::Function { body: None, .. }
+            ));
+        } else {
+            panic!("Item is not a trait");
+        }
+        if let ItemJson::Impl { items, .. } = &ast.items[10].kind {
+            assert!(matches!(
+                &items[0].kind,
+                ItemJson::Function { body: Some(_), .. }
+            ));
+        } else {
+            panic!("Item is not an impl");
+        }
+    }
+}
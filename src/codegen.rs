@@ -0,0 +1,1519 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use serde::Serialize;
+use serde_json::Value;
+use syn::File;
+
+use crate::attribute::joined_docs;
+use crate::builder::build_type;
+use crate::model::{
+    AstJson, AttributeJson, ExprJson, FieldJson, FieldsKind, GenericParamJson, GenericsJson,
+    ItemJson, MacroDelimiter, Node, ParameterJson, PatternJson, StmtJson, TypeJson, VariantJson,
+    VisibilityJson, WherePredicateJson,
+};
+
+impl AstJson {
+    /// to_file
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the model, for example one read back from JSON
+    ///
+    /// # Returns
+    /// * `syn::Result<File>` - the syntax tree the model describes, or an error when a
+    ///   string in the model such as a type or a path is not valid Rust
+    pub fn to_file(&self) -> syn::Result<File> {
+        let docs = doc_tokens(&self.docs, &self.attributes, true)?;
+        let attrs = attribute_tokens(&self.attributes, true)?;
+        let items = self
+            .items
+            .iter()
+            .map(|item| item_tokens(item, false))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        syn::parse2(quote! { #docs #attrs #(#items)* })
+    }
+
+    /// to_source
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the model, for example one read back from JSON
+    ///
+    /// # Returns
+    /// * `syn::Result<String>` - formatted Rust source code for the model
+    pub fn to_source(&self) -> syn::Result<String> {
+        Ok(prettyplease::unparse(&self.to_file()?))
+    }
+}
+
+/// text
+///
+/// # Arguments
+/// * `source`: &str - a piece of Rust source kept as a string in the model, such as a type
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the tokens of the source
+fn text(source: &str) -> syn::Result<TokenStream> {
+    Ok(source.parse::<TokenStream>()?)
+}
+
+/// optional_text
+///
+/// # Arguments
+/// * `source`: &Option<String> - an optional piece of Rust source
+///
+/// # Returns
+/// * `syn::Result<Option<TokenStream>>` - the tokens of the source, if any
+fn optional_text(source: &Option<String>) -> syn::Result<Option<TokenStream>> {
+    source.as_deref().map(text).transpose()
+}
+
+/// type_tokens
+///
+/// Code is generated from the type as written; its structure is only checked against it, so
+/// that an edit to only one of the two is reported instead of being lost.
+///
+/// # Arguments
+/// * `type_info`: &str - a type as written, such as `Vec<&'a str>`
+/// * `type_tree`: &Node<TypeJson> - the structure of the same type
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the tokens of the type, or an error when the two disagree
+fn type_tokens(type_info: &str, type_tree: &Node<TypeJson>) -> syn::Result<TokenStream> {
+    let ty: syn::Type = syn::parse_str(type_info)?;
+    check_type_tree(type_info, &build_type(&ty), type_tree)?;
+    text(type_info)
+}
+
+/// optional_type_tokens
+///
+/// # Arguments
+/// * `type_info`: &Option<String> - an optional type as written
+/// * `type_tree`: Option<&Node<TypeJson>> - the structure of the same type, if the model has it
+///
+/// # Returns
+/// * `syn::Result<Option<TokenStream>>` - the tokens of the type, or an error when a structure
+///   is given that does not match
+fn optional_type_tokens(
+    type_info: &Option<String>,
+    type_tree: Option<&Node<TypeJson>>,
+) -> syn::Result<Option<TokenStream>> {
+    match (type_info, type_tree) {
+        (Some(type_info), Some(type_tree)) => type_tokens(type_info, type_tree).map(Some),
+        // a model written by hand may leave out the structure
+        (Some(type_info), None) => text(type_info).map(Some),
+        (None, Some(_)) => Err(syn::Error::new(
+            Span::call_site(),
+            "a type structure is given without the type as written",
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// check_type_tree
+///
+/// # Arguments
+/// * `type_info`: &str - a type as written
+/// * `built`: &Node<TypeJson> - the structure built from `type_info`
+/// * `type_tree`: &Node<TypeJson> - the structure recorded in the model
+///
+/// # Returns
+/// * `syn::Result<()>` - an error when the recorded structure, spans aside, is a different type
+fn check_type_tree(
+    type_info: &str,
+    built: &Node<TypeJson>,
+    type_tree: &Node<TypeJson>,
+) -> syn::Result<()> {
+    if without_spans(built) != without_spans(type_tree) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "the type `{}` does not match its structure; the type as written is what gets \
+                 generated, so edit it and its structure together",
+                type_info
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// without_spans
+///
+/// # Arguments
+/// * `node`: &impl Serialize - a part of the model
+///
+/// # Returns
+/// * `Value` - the JSON form of the node with every `span` removed
+fn without_spans(node: &impl Serialize) -> Value {
+    let mut value = serde_json::to_value(node).unwrap_or(Value::Null);
+    strip_spans(&mut value);
+    value
+}
+
+/// strip_spans
+///
+/// # Arguments
+/// * `value`: &mut Value - the JSON form of a part of the model
+fn strip_spans(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("span");
+            map.values_mut().for_each(strip_spans);
+        }
+        Value::Array(values) => values.iter_mut().for_each(strip_spans),
+        _ => {}
+    }
+}
+
+/// doc_tokens
+///
+/// Doc comments are normally written from the `doc` attributes in `attributes`, which keep
+/// their text and position; `docs` is only used for a model that has none, such as one
+/// written by hand. When both are present they must agree, so that an edit to only one of
+/// them is reported instead of being lost.
+///
+/// # Arguments
+/// * `docs`: &Option<String> - doc comments, one line per `///` line
+/// * `attributes`: &[AttributeJson] - the attributes of the same node
+/// * `inner`: bool - whether to write `//!` comments instead of `///` comments
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - one `doc` attribute per line, or an error when `docs` does
+///   not match the doc attributes
+fn doc_tokens(
+    docs: &Option<String>,
+    attributes: &[AttributeJson],
+    inner: bool,
+) -> syn::Result<TokenStream> {
+    if attributes.iter().any(|attr| attr.doc.is_some()) {
+        if joined_docs(attributes) != *docs {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "`docs` {:?} does not match the doc attributes of the same node; \
+                     edit both, or remove the doc attributes to write `docs` alone",
+                    docs.as_deref().unwrap_or_default()
+                ),
+            ));
+        }
+        return Ok(TokenStream::new());
+    }
+
+    let lines = docs.iter().flat_map(|docs| docs.split('\n')).map(|line| {
+        // the builder strips the space that follows `///`, so it is put back here
+        let line = if line.is_empty() {
+            Literal::string("")
+        } else {
+            Literal::string(&format!(" {}", line))
+        };
+        if inner {
+            quote!(#![doc = #line])
+        } else {
+            quote!(#[doc = #line])
+        }
+    });
+    Ok(quote!(#(#lines)*))
+}
+
+/// attribute_tokens
+///
+/// # Arguments
+/// * `attributes`: &[AttributeJson] - the attributes of a node
+/// * `inner`: bool - whether to write the inner attributes instead of the outer ones
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the selected attributes
+fn attribute_tokens(attributes: &[AttributeJson], inner: bool) -> syn::Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    for attribute in attributes.iter().filter(|attr| attr.inner == inner) {
+        let path = text(&attribute.path)?;
+        let meta = match (&attribute.doc, &attribute.arguments, &attribute.value) {
+            (Some(doc), _, _) => {
+                let doc = Literal::string(doc);
+                quote!(#path = #doc)
+            }
+            (None, Some(arguments), _) => {
+                let arguments = text(arguments)?;
+                quote!(#path(#arguments))
+            }
+            (None, None, Some(value)) => {
+                let value = text(value)?;
+                quote!(#path = #value)
+            }
+            (None, None, None) => path,
+        };
+        tokens.extend(if inner {
+            quote!(#![#meta])
+        } else {
+            quote!(#[#meta])
+        });
+    }
+    Ok(tokens)
+}
+
+/// outer_attribute_tokens
+///
+/// # Arguments
+/// * `attributes`: &[AttributeJson] - the attributes of a node
+/// * `docs`: &Option<String> - the doc comments of a node
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the outer attributes, doc comments included
+fn outer_attribute_tokens(
+    attributes: &[AttributeJson],
+    docs: &Option<String>,
+) -> syn::Result<TokenStream> {
+    let mut tokens = doc_tokens(docs, attributes, false)?;
+    tokens.extend(attribute_tokens(attributes, false)?);
+    Ok(tokens)
+}
+
+/// visibility_tokens
+///
+/// # Arguments
+/// * `visibility`: &Option<VisibilityJson> - the visibility of an item or field
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the visibility, empty for a private item or field
+fn visibility_tokens(visibility: &Option<VisibilityJson>) -> syn::Result<TokenStream> {
+    Ok(match visibility {
+        None => TokenStream::new(),
+        Some(VisibilityJson::Public) => quote!(pub),
+        Some(VisibilityJson::Crate) => quote!(pub(crate)),
+        Some(VisibilityJson::Restricted { path }) if path == "self" || path == "super" => {
+            let path = text(path)?;
+            quote!(pub(#path))
+        }
+        Some(VisibilityJson::Restricted { path }) => {
+            let path = text(path)?;
+            quote!(pub(in #path))
+        }
+    })
+}
+
+/// bounds_tokens
+///
+/// # Arguments
+/// * `bounds`: &[String] - trait or lifetime bounds
+///
+/// # Returns
+/// * `syn::Result<Option<TokenStream>>` - the bounds joined with `+` after a colon,
+///   or None when there are no bounds
+fn bounds_tokens(bounds: &[String]) -> syn::Result<Option<TokenStream>> {
+    if bounds.is_empty() {
+        return Ok(None);
+    }
+    let bounds = text(&bounds.join(" + "))?;
+    Ok(Some(quote!(: #bounds)))
+}
+
+/// generics_tokens
+///
+/// # Arguments
+/// * `generics`: &GenericsJson - the generics of an item
+///
+/// # Returns
+/// * `syn::Result<(TokenStream, TokenStream)>` - the `<...>` parameter list and the `where` clause,
+///   each empty when absent
+fn generics_tokens(generics: &GenericsJson) -> syn::Result<(TokenStream, TokenStream)> {
+    let mut params = Vec::new();
+    for param in &generics.params {
+        params.push(match param {
+            GenericParamJson::Type {
+                name,
+                bounds,
+                default,
+            } => {
+                let name = text(name)?;
+                let bounds = bounds_tokens(bounds)?;
+                let default = optional_text(default)?.map(|default| quote!(= #default));
+                quote!(#name #bounds #default)
+            }
+            GenericParamJson::Lifetime { name, bounds } => {
+                let name = text(name)?;
+                let bounds = bounds_tokens(bounds)?;
+                quote!(#name #bounds)
+            }
+            GenericParamJson::Const {
+                name,
+                type_info,
+                default,
+            } => {
+                let name = text(name)?;
+                let ty = text(type_info)?;
+                let default = optional_text(default)?.map(|default| quote!(= #default));
+                quote!(const #name: #ty #default)
+            }
+        });
+    }
+
+    let mut predicates = Vec::new();
+    for predicate in &generics.where_clause {
+        predicates.push(match predicate {
+            WherePredicateJson::Type {
+                lifetimes,
+                bounded_type,
+                bounds,
+            } => {
+                let lifetimes = if lifetimes.is_empty() {
+                    None
+                } else {
+                    let lifetimes = text(&lifetimes.join(", "))?;
+                    Some(quote!(for<#lifetimes>))
+                };
+                let bounded_type = text(bounded_type)?;
+                let bounds = text(&bounds.join(" + "))?;
+                quote!(#lifetimes #bounded_type: #bounds)
+            }
+            WherePredicateJson::Lifetime { lifetime, bounds } => {
+                let lifetime = text(lifetime)?;
+                let bounds = text(&bounds.join(" + "))?;
+                quote!(#lifetime: #bounds)
+            }
+        });
+    }
+
+    let params = if params.is_empty() {
+        TokenStream::new()
+    } else {
+        quote!(<#(#params),*>)
+    };
+    let where_clause = if predicates.is_empty() {
+        TokenStream::new()
+    } else {
+        quote!(where #(#predicates),*)
+    };
+    Ok((params, where_clause))
+}
+
+/// item_tokens
+///
+/// # Arguments
+/// * `item`: &Node<ItemJson> - the item to write
+/// * `associated`: bool - whether the item sits in an impl block or a trait, where the where
+///   clause of a type follows its `=` instead of preceding it
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the item with its attributes
+fn item_tokens(item: &Node<ItemJson>, associated: bool) -> syn::Result<TokenStream> {
+    let outer = outer_attribute_tokens(&item.attributes, &item.docs)?;
+    // the description already contains the inner attributes of the item
+    if let ItemJson::Other { description } = &item.kind {
+        let description = text(description)?;
+        return Ok(quote!(#outer #description));
+    }
+
+    let inner = attribute_tokens(&item.attributes, true)?;
+    let tokens = match &item.kind {
+        ItemJson::Function {
+            name,
+            visibility,
+            is_const,
+            is_async,
+            is_unsafe,
+            is_extern,
+            abi,
+            generics,
+            parameters,
+            variadic,
+            return_type,
+            return_type_tree,
+            body,
+            ..
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let constness = is_const.then(|| quote!(const));
+            let asyncness = is_async.then(|| quote!(async));
+            let unsafety = is_unsafe.then(|| quote!(unsafe));
+            let abi = is_extern.then(|| {
+                let name = abi.as_deref().map(Literal::string);
+                quote!(extern #name)
+            });
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+
+            let mut inputs = parameters
+                .iter()
+                .map(parameter_tokens)
+                .collect::<syn::Result<Vec<_>>>()?;
+            if *variadic {
+                inputs.push(quote!(...));
+            }
+
+            let output = optional_type_tokens(return_type, return_type_tree.as_ref())?
+                .map(|ty| quote!(-> #ty));
+            let body = match body {
+                Some(body) => block_tokens(inner, body)?,
+                None => quote!(;),
+            };
+            quote! {
+                #vis #constness #asyncness #unsafety #abi fn #name #params (#(#inputs),*) #output
+                #where_clause #body
+            }
+        }
+        ItemJson::Struct {
+            name,
+            visibility,
+            generics,
+            kind,
+            fields,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+            let fields = fields_tokens(*kind, fields)?;
+            match kind {
+                FieldsKind::Named => quote!(#vis struct #name #params #where_clause #fields),
+                _ => quote!(#vis struct #name #params #fields #where_clause;),
+            }
+        }
+        ItemJson::Enum {
+            name,
+            visibility,
+            generics,
+            variants,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+            let variants = variants
+                .iter()
+                .map(variant_tokens)
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(#vis enum #name #params #where_clause { #(#variants),* })
+        }
+        ItemJson::Union {
+            name,
+            visibility,
+            generics,
+            fields,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+            let fields = fields_tokens(FieldsKind::Named, fields)?;
+            quote!(#vis union #name #params #where_clause #fields)
+        }
+        ItemJson::Impl {
+            generics,
+            is_default,
+            is_unsafe,
+            negative,
+            self_type,
+            self_type_tree,
+            trait_name,
+            items,
+        } => {
+            let defaultness = is_default.then(|| quote!(default));
+            let unsafety = is_unsafe.then(|| quote!(unsafe));
+            let (params, where_clause) = generics_tokens(generics)?;
+            let negative = negative.then(|| quote!(!));
+            let trait_name = optional_text(trait_name)?.map(|path| quote!(#negative #path for));
+            let self_type = type_tokens(self_type, self_type_tree)?;
+            let items = items_tokens(items, true)?;
+            quote!(#defaultness #unsafety impl #params #trait_name #self_type #where_clause { #inner #items })
+        }
+        ItemJson::Trait {
+            name,
+            visibility,
+            generics,
+            is_unsafe,
+            is_auto,
+            supertraits,
+            items,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let unsafety = is_unsafe.then(|| quote!(unsafe));
+            let auto = is_auto.then(|| quote!(auto));
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+            let supertraits = bounds_tokens(supertraits)?;
+            let items = items_tokens(items, true)?;
+            quote! {
+                #vis #unsafety #auto trait #name #params #supertraits #where_clause { #inner #items }
+            }
+        }
+        ItemJson::Module {
+            name,
+            visibility,
+            is_unsafe,
+            items,
+            ..
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let unsafety = is_unsafe.then(|| quote!(unsafe));
+            let name = text(name)?;
+            match items {
+                Some(items) => {
+                    let items = items_tokens(items, false)?;
+                    quote!(#vis #unsafety mod #name { #inner #items })
+                }
+                None => quote!(#vis #unsafety mod #name;),
+            }
+        }
+        ItemJson::Use {
+            visibility,
+            leading_colon,
+            tree,
+            ..
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let leading_colon = leading_colon.then(|| quote!(::));
+            let tree = text(tree)?;
+            quote!(#vis use #leading_colon #tree;)
+        }
+        ItemJson::ExternCrate {
+            name,
+            visibility,
+            alias,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let alias = optional_text(alias)?.map(|alias| quote!(as #alias));
+            quote!(#vis extern crate #name #alias;)
+        }
+        ItemJson::Const {
+            name,
+            visibility,
+            type_info,
+            type_tree,
+            value,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let ty = type_tokens(type_info, type_tree)?;
+            let value = value
+                .as_deref()
+                .map(expr_tokens)
+                .transpose()?
+                .map(|value| quote!(= #value));
+            quote!(#vis const #name: #ty #value;)
+        }
+        ItemJson::Static {
+            name,
+            visibility,
+            mutable,
+            type_info,
+            type_tree,
+            value,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let mutability = mutable.then(|| quote!(mut));
+            let name = text(name)?;
+            let ty = type_tokens(type_info, type_tree)?;
+            let value = value
+                .as_deref()
+                .map(expr_tokens)
+                .transpose()?
+                .map(|value| quote!(= #value));
+            quote!(#vis static #mutability #name: #ty #value;)
+        }
+        ItemJson::TypeAlias {
+            name,
+            visibility,
+            generics,
+            bounds,
+            type_info,
+            type_tree,
+        } => {
+            let vis = visibility_tokens(visibility)?;
+            let name = text(name)?;
+            let (params, where_clause) = generics_tokens(generics)?;
+            let bounds = bounds_tokens(bounds)?;
+            let ty = optional_type_tokens(type_info, type_tree.as_ref())?.map(|ty| quote!(= #ty));
+            if associated {
+                quote!(#vis type #name #params #bounds #ty #where_clause;)
+            } else {
+                quote!(#vis type #name #params #bounds #where_clause #ty;)
+            }
+        }
+        ItemJson::Macro {
+            name,
+            path,
+            delimiter,
+            tokens,
+        } => {
+            let path = text(path)?;
+            let name = optional_text(name)?;
+            let tokens = text(tokens)?;
+            match delimiter {
+                MacroDelimiter::Paren => quote!(#path! #name (#tokens);),
+                MacroDelimiter::Bracket => quote!(#path! #name [#tokens];),
+                MacroDelimiter::Brace => quote!(#path! #name { #tokens }),
+            }
+        }
+        ItemJson::MacroRules { name, rules } => {
+            let name = text(name)?;
+            let mut arms = Vec::new();
+            for rule in rules {
+                let matcher = text(&rule.matcher)?;
+                let transcriber = text(&rule.transcriber)?;
+                arms.push(quote!(#matcher => #transcriber;));
+            }
+            quote!(macro_rules! #name { #(#arms)* })
+        }
+        ItemJson::ExternBlock {
+            is_unsafe,
+            abi,
+            items,
+        } => {
+            let unsafety = is_unsafe.then(|| quote!(unsafe));
+            let abi = abi.as_deref().map(Literal::string);
+            let items = items_tokens(items, false)?;
+            quote!(#unsafety extern #abi { #inner #items })
+        }
+        ItemJson::Other { .. } => unreachable!("handled above"),
+    };
+
+    Ok(quote!(#outer #tokens))
+}
+
+/// items_tokens
+///
+/// # Arguments
+/// * `items`: &[Node<ItemJson>] - the items of an impl, trait, module or extern block
+/// * `associated`: bool - whether the items belong to an impl block or a trait
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - every item in order
+fn items_tokens(items: &[Node<ItemJson>], associated: bool) -> syn::Result<TokenStream> {
+    let items = items
+        .iter()
+        .map(|item| item_tokens(item, associated))
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote!(#(#items)*))
+}
+
+/// parameter_tokens
+///
+/// # Arguments
+/// * `parameter`: &ParameterJson - a function parameter
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - `pattern: Type`, or the receiver as written
+fn parameter_tokens(parameter: &ParameterJson) -> syn::Result<TokenStream> {
+    let attrs = attribute_tokens(&parameter.attributes, false)?;
+    Ok(match &parameter.pattern {
+        Some(pattern) => {
+            let pattern = pattern_tokens(pattern)?;
+            let ty = type_tokens(&parameter.type_info, &parameter.type_tree)?;
+            quote!(#attrs #pattern: #ty)
+        }
+        // the type of a receiver is kept as written, as in `&mut self`
+        None => {
+            let receiver: syn::Receiver = syn::parse_str(&parameter.type_info)?;
+            check_type_tree(
+                &parameter.type_info,
+                &build_type(&receiver.ty),
+                &parameter.type_tree,
+            )?;
+            let receiver = text(&parameter.type_info)?;
+            quote!(#attrs #receiver)
+        }
+    })
+}
+
+/// fields_tokens
+///
+/// # Arguments
+/// * `kind`: FieldsKind - whether the fields are named, positional or absent
+/// * `fields`: &[FieldJson] - the fields of a struct, union or enum variant
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the fields in braces or parentheses, empty for a unit
+fn fields_tokens(kind: FieldsKind, fields: &[FieldJson]) -> syn::Result<TokenStream> {
+    let mut tokens = Vec::new();
+    for field in fields {
+        let attrs = outer_attribute_tokens(&field.attributes, &field.docs)?;
+        let vis = visibility_tokens(&field.visibility)?;
+        let name = optional_text(&field.name)?.map(|name| quote!(#name:));
+        let ty = type_tokens(&field.type_info, &field.type_tree)?;
+        tokens.push(quote!(#attrs #vis #name #ty));
+    }
+
+    Ok(match kind {
+        FieldsKind::Named => quote!({ #(#tokens),* }),
+        FieldsKind::Tuple => quote!((#(#tokens),*)),
+        FieldsKind::Unit => TokenStream::new(),
+    })
+}
+
+/// variant_tokens
+///
+/// # Arguments
+/// * `variant`: &VariantJson - an enum variant
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the variant with its attributes, fields and discriminant
+fn variant_tokens(variant: &VariantJson) -> syn::Result<TokenStream> {
+    let attrs = outer_attribute_tokens(&variant.attributes, &variant.docs)?;
+    let name = text(&variant.name)?;
+    let fields = fields_tokens(variant.kind, &variant.fields)?;
+    let discriminant = variant
+        .discriminant
+        .as_ref()
+        .map(expr_tokens)
+        .transpose()?
+        .map(|expr| quote!(= #expr));
+    Ok(quote!(#attrs #name #fields #discriminant))
+}
+
+/// block_tokens
+///
+/// # Arguments
+/// * `inner`: TokenStream - inner attributes written at the top of the block
+/// * `stmts`: &[Node<StmtJson>] - the statements of the block
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the statements in braces
+fn block_tokens(inner: TokenStream, stmts: &[Node<StmtJson>]) -> syn::Result<TokenStream> {
+    let stmts = stmts
+        .iter()
+        .map(stmt_tokens)
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote!({ #inner #(#stmts)* }))
+}
+
+/// stmt_tokens
+///
+/// # Arguments
+/// * `stmt`: &Node<StmtJson> - the statement to write
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the statement with its attributes
+fn stmt_tokens(stmt: &Node<StmtJson>) -> syn::Result<TokenStream> {
+    Ok(match &stmt.kind {
+        StmtJson::VariableDeclaration {
+            pattern,
+            type_info,
+            type_tree,
+            initializer,
+            else_branch,
+            ..
+        } => {
+            let attrs = outer_attribute_tokens(&stmt.attributes, &stmt.docs)?;
+            let pattern = pattern_tokens(pattern)?;
+            let ty = optional_type_tokens(type_info, type_tree.as_deref())?.map(|ty| quote!(: #ty));
+            let init = initializer
+                .as_deref()
+                .map(expr_tokens)
+                .transpose()?
+                .map(|init| quote!(= #init));
+            let diverge = else_branch
+                .as_deref()
+                .map(expr_tokens)
+                .transpose()?
+                .map(|diverge| quote!(else #diverge));
+            quote!(#attrs let #pattern #ty #init #diverge;)
+        }
+        StmtJson::Expression { expr, semicolon } => {
+            let expr = expr_tokens(expr)?;
+            let semi = semicolon.then(|| quote!(;));
+            quote!(#expr #semi)
+        }
+        StmtJson::Item { item } => item_tokens(item, false)?,
+        StmtJson::Other { description } => {
+            let attrs = outer_attribute_tokens(&stmt.attributes, &stmt.docs)?;
+            let description = text(description)?;
+            quote!(#attrs #description)
+        }
+    })
+}
+
+/// label_tokens
+///
+/// # Arguments
+/// * `label`: &Option<String> - the label of a loop or block
+///
+/// # Returns
+/// * `syn::Result<Option<TokenStream>>` - the label followed by a colon, if any
+fn label_tokens(label: &Option<String>) -> syn::Result<Option<TokenStream>> {
+    Ok(optional_text(label)?.map(|label| quote!(#label:)))
+}
+
+/// exprs_tokens
+///
+/// # Arguments
+/// * `exprs`: &[Node<ExprJson>] - a list of expressions, such as call arguments
+///
+/// # Returns
+/// * `syn::Result<Vec<TokenStream>>` - the tokens of every expression
+fn exprs_tokens(exprs: &[Node<ExprJson>]) -> syn::Result<Vec<TokenStream>> {
+    exprs.iter().map(expr_tokens).collect()
+}
+
+/// expr_tokens
+///
+/// # Arguments
+/// * `expr`: &Node<ExprJson> - the expression to write
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the expression with its attributes
+fn expr_tokens(expr: &Node<ExprJson>) -> syn::Result<TokenStream> {
+    let outer = outer_attribute_tokens(&expr.attributes, &expr.docs)?;
+    // the description already contains the inner attributes of the expression
+    if let ExprJson::Other { description } = &expr.kind {
+        let description = text(description)?;
+        return Ok(quote!(#outer #description));
+    }
+
+    // inner attributes of a block-like expression sit at the top of its block
+    let inner = attribute_tokens(&expr.attributes, true)?;
+    let tokens = match &expr.kind {
+        ExprJson::IntLiteral { raw, .. }
+        | ExprJson::FloatLiteral { raw, .. }
+        | ExprJson::StringLiteral { raw, .. }
+        | ExprJson::CharLiteral { raw, .. }
+        | ExprJson::ByteLiteral { raw, .. }
+        | ExprJson::ByteStringLiteral { raw, .. }
+        | ExprJson::CStringLiteral { raw, .. }
+        | ExprJson::VerbatimLiteral { raw } => text(raw)?,
+        ExprJson::BoolLiteral { value } => quote!(#value),
+        ExprJson::StructLiteral { path, fields, base } => {
+            let path = text(path)?;
+            let mut parts = Vec::new();
+            for field in fields {
+                let attrs = outer_attribute_tokens(&field.attributes, &field.docs)?;
+                let member = text(&field.member)?;
+                if field.shorthand {
+                    parts.push(quote!(#attrs #member));
+                } else {
+                    let value = expr_tokens(&field.value)?;
+                    parts.push(quote!(#attrs #member: #value));
+                }
+            }
+            if let Some(base) = base {
+                let base = expr_tokens(base)?;
+                parts.push(quote!(..#base));
+            }
+            quote!(#path { #(#parts),* })
+        }
+        ExprJson::Tuple { elements } => {
+            let elements = exprs_tokens(elements)?;
+            if elements.len() == 1 {
+                quote!((#(#elements),*,))
+            } else {
+                quote!((#(#elements),*))
+            }
+        }
+        ExprJson::Array { elements } => {
+            let elements = exprs_tokens(elements)?;
+            quote!([#(#elements),*])
+        }
+        ExprJson::ArrayRepeat { value, length } => {
+            let value = expr_tokens(value)?;
+            let length = expr_tokens(length)?;
+            quote!([#value; #length])
+        }
+        ExprJson::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let start = start.as_deref().map(expr_tokens).transpose()?;
+            let end = end.as_deref().map(expr_tokens).transpose()?;
+            if *inclusive {
+                quote!(#start ..= #end)
+            } else {
+                quote!(#start .. #end)
+            }
+        }
+        ExprJson::Parenthesized { expr } => {
+            let expr = expr_tokens(expr)?;
+            quote!((#expr))
+        }
+        ExprJson::Binary {
+            operator,
+            left,
+            right,
+            ..
+        } => {
+            let left = expr_tokens(left)?;
+            let operator = text(operator)?;
+            let right = expr_tokens(right)?;
+            quote!(#left #operator #right)
+        }
+        ExprJson::FunctionCall {
+            function,
+            arguments,
+        } => {
+            let function = expr_tokens(function)?;
+            let arguments = exprs_tokens(arguments)?;
+            quote!(#function(#(#arguments),*))
+        }
+        ExprJson::Identifier { name } => text(name)?,
+        ExprJson::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            let condition = expr_tokens(condition)?;
+            let then_branch = block_tokens(TokenStream::new(), then_branch)?;
+            let else_branch = else_branch
+                .as_deref()
+                .map(expr_tokens)
+                .transpose()?
+                .map(|else_branch| quote!(else #else_branch));
+            quote!(if #condition #then_branch #else_branch)
+        }
+        ExprJson::Loop { label, body } => {
+            let label = label_tokens(label)?;
+            let body = block_tokens(inner, body)?;
+            quote!(#label loop #body)
+        }
+        ExprJson::While {
+            label,
+            condition,
+            body,
+        } => {
+            let label = label_tokens(label)?;
+            let condition = expr_tokens(condition)?;
+            let body = block_tokens(inner, body)?;
+            quote!(#label while #condition #body)
+        }
+        ExprJson::ForLoop {
+            label,
+            pattern,
+            iterable,
+            body,
+        } => {
+            let label = label_tokens(label)?;
+            let pattern = pattern_tokens(pattern)?;
+            let iterable = expr_tokens(iterable)?;
+            let body = block_tokens(inner, body)?;
+            quote!(#label for #pattern in #iterable #body)
+        }
+        ExprJson::Break { label, value } => {
+            let label = optional_text(label)?;
+            let value = value.as_deref().map(expr_tokens).transpose()?;
+            quote!(break #label #value)
+        }
+        ExprJson::Continue { label } => {
+            let label = optional_text(label)?;
+            quote!(continue #label)
+        }
+        ExprJson::Return { value } => {
+            let value = value.as_deref().map(expr_tokens).transpose()?;
+            quote!(return #value)
+        }
+        ExprJson::Let { pattern, expr } => {
+            let pattern = pattern_tokens(pattern)?;
+            let expr = expr_tokens(expr)?;
+            quote!(let #pattern = #expr)
+        }
+        ExprJson::Try { expr } => {
+            let expr = expr_tokens(expr)?;
+            quote!(#expr?)
+        }
+        ExprJson::Await { expr } => {
+            let expr = expr_tokens(expr)?;
+            quote!(#expr.await)
+        }
+        ExprJson::Match { expr, arms } => {
+            let expr = expr_tokens(expr)?;
+            let mut tokens = Vec::new();
+            for arm in arms {
+                let attrs = outer_attribute_tokens(&arm.attributes, &arm.docs)?;
+                let pattern = pattern_tokens(&arm.pattern)?;
+                let guard = arm
+                    .guard
+                    .as_deref()
+                    .map(expr_tokens)
+                    .transpose()?
+                    .map(|guard| quote!(if #guard));
+                let body = expr_tokens(&arm.body)?;
+                tokens.push(quote!(#attrs #pattern #guard => #body,));
+            }
+            quote!(match #expr { #inner #(#tokens)* })
+        }
+        ExprJson::Closure {
+            lifetimes,
+            is_const,
+            is_static,
+            is_move,
+            is_async,
+            parameters,
+            return_type,
+            return_type_tree,
+            body,
+        } => {
+            let lifetimes = if lifetimes.is_empty() {
+                None
+            } else {
+                let lifetimes = text(&lifetimes.join(", "))?;
+                Some(quote!(for<#lifetimes>))
+            };
+            let constness = is_const.then(|| quote!(const));
+            let movability = is_static.then(|| quote!(static));
+            let asyncness = is_async.then(|| quote!(async));
+            let capture = is_move.then(|| quote!(move));
+            let mut inputs = Vec::new();
+            for param in parameters {
+                let pattern = pattern_tokens(&param.pattern)?;
+                let ty = optional_type_tokens(&param.type_info, param.type_tree.as_ref())?
+                    .map(|ty| quote!(: #ty));
+                inputs.push(quote!(#pattern #ty));
+            }
+            let output = optional_type_tokens(return_type, return_type_tree.as_ref())?
+                .map(|ty| quote!(-> #ty));
+            let body = expr_tokens(body)?;
+            quote!(#lifetimes #constness #movability #asyncness #capture |#(#inputs),*| #output #body)
+        }
+        ExprJson::MethodCall {
+            receiver,
+            method,
+            turbofish,
+            arguments,
+        } => {
+            let receiver = expr_tokens(receiver)?;
+            let method = text(method)?;
+            let turbofish = if turbofish.is_empty() {
+                None
+            } else {
+                let arguments = turbofish
+                    .iter()
+                    .map(|argument| text(argument))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Some(quote!(::<#(#arguments),*>))
+            };
+            let arguments = exprs_tokens(arguments)?;
+            quote!(#receiver.#method #turbofish(#(#arguments),*))
+        }
+        ExprJson::FieldAccess { base, member } => {
+            let base = expr_tokens(base)?;
+            let member = text(member)?;
+            quote!(#base.#member)
+        }
+        ExprJson::Index { base, index } => {
+            let base = expr_tokens(base)?;
+            let index = expr_tokens(index)?;
+            quote!(#base[#index])
+        }
+        ExprJson::Unary {
+            operator, operand, ..
+        } => {
+            let operator = text(operator)?;
+            let operand = expr_tokens(operand)?;
+            quote!(#operator #operand)
+        }
+        ExprJson::Reference { mutable, expr } => {
+            let mutability = mutable.then(|| quote!(mut));
+            let expr = expr_tokens(expr)?;
+            quote!(&#mutability #expr)
+        }
+        ExprJson::Cast {
+            expr,
+            type_info,
+            type_tree,
+        } => {
+            let expr = expr_tokens(expr)?;
+            let ty = type_tokens(type_info, type_tree)?;
+            quote!(#expr as #ty)
+        }
+        ExprJson::Assign { target, value } => {
+            let target = expr_tokens(target)?;
+            let value = expr_tokens(value)?;
+            quote!(#target = #value)
+        }
+        ExprJson::CompoundAssign {
+            operator,
+            target,
+            value,
+            ..
+        } => {
+            let target = expr_tokens(target)?;
+            let operator = text(operator)?;
+            let value = expr_tokens(value)?;
+            quote!(#target #operator #value)
+        }
+        ExprJson::Block { label, body } => {
+            let label = label_tokens(label)?;
+            let body = block_tokens(inner, body)?;
+            quote!(#label #body)
+        }
+        ExprJson::AsyncBlock { is_move, body } => {
+            let capture = is_move.then(|| quote!(move));
+            let body = block_tokens(inner, body)?;
+            quote!(async #capture #body)
+        }
+        ExprJson::UnsafeBlock { body } => {
+            let body = block_tokens(inner, body)?;
+            quote!(unsafe #body)
+        }
+        ExprJson::ConstBlock { body } => {
+            let body = block_tokens(inner, body)?;
+            quote!(const #body)
+        }
+        ExprJson::Other { .. } => unreachable!("handled above"),
+    };
+
+    Ok(quote!(#outer #tokens))
+}
+
+/// pattern_tokens
+///
+/// # Arguments
+/// * `pattern`: &Node<PatternJson> - the pattern to write
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the pattern
+fn pattern_tokens(pattern: &Node<PatternJson>) -> syn::Result<TokenStream> {
+    let patterns = |patterns: &[Node<PatternJson>]| {
+        patterns
+            .iter()
+            .map(pattern_tokens)
+            .collect::<syn::Result<Vec<_>>>()
+    };
+
+    Ok(match &pattern.kind {
+        PatternJson::Identifier {
+            name,
+            by_ref,
+            mutable,
+            subpattern,
+        } => {
+            let by_ref = by_ref.then(|| quote!(ref));
+            let mutability = mutable.then(|| quote!(mut));
+            let name = text(name)?;
+            let subpattern = subpattern
+                .as_deref()
+                .map(grouped_pattern_tokens)
+                .transpose()?
+                .map(|subpattern| quote!(@ #subpattern));
+            quote!(#by_ref #mutability #name #subpattern)
+        }
+        PatternJson::Wildcard => quote!(_),
+        PatternJson::Rest => quote!(..),
+        PatternJson::Literal { value } => expr_tokens(value)?,
+        PatternJson::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let start = start.as_deref().map(expr_tokens).transpose()?;
+            let end = end.as_deref().map(expr_tokens).transpose()?;
+            if *inclusive {
+                quote!(#start ..= #end)
+            } else {
+                quote!(#start .. #end)
+            }
+        }
+        PatternJson::Path { path } => text(path)?,
+        PatternJson::Tuple { elements } => {
+            // `(a,)` is a tuple while `(a)` only groups, but `(..)` is a tuple either way
+            let trailing_comma = matches!(
+                elements.as_slice(),
+                [element] if !matches!(element.kind, PatternJson::Rest)
+            )
+            .then(|| quote!(,));
+            let elements = patterns(elements)?;
+            quote!((#(#elements),* #trailing_comma))
+        }
+        PatternJson::TupleStruct { path, elements } => {
+            let path = text(path)?;
+            let elements = patterns(elements)?;
+            quote!(#path(#(#elements),*))
+        }
+        PatternJson::Struct {
+            path,
+            fields,
+            has_rest,
+        } => {
+            let path = text(path)?;
+            let mut parts = Vec::new();
+            for field in fields {
+                let attrs = attribute_tokens(&field.attributes, false)?;
+                let pattern = pattern_tokens(&field.pattern)?;
+                if field.shorthand {
+                    parts.push(quote!(#attrs #pattern));
+                } else {
+                    let member = text(&field.member)?;
+                    parts.push(quote!(#attrs #member: #pattern));
+                }
+            }
+            if *has_rest {
+                parts.push(quote!(..));
+            }
+            quote!(#path { #(#parts),* })
+        }
+        PatternJson::Slice { elements } => {
+            let elements = patterns(elements)?;
+            quote!([#(#elements),*])
+        }
+        PatternJson::Reference { mutable, pattern } => {
+            let mutability = mutable.then(|| quote!(mut));
+            let pattern = referenced_pattern_tokens(pattern)?;
+            quote!(&#mutability #pattern)
+        }
+        PatternJson::Or { cases } => {
+            let cases = cases
+                .iter()
+                .map(grouped_pattern_tokens)
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(#(#cases)|*)
+        }
+        PatternJson::Other { description } => text(description)?,
+    })
+}
+
+/// grouped_pattern_tokens
+///
+/// # Arguments
+/// * `pattern`: &Node<PatternJson> - a pattern nested after `@`, after `&` or inside another or-pattern
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the pattern, in parentheses when it is an or-pattern
+fn grouped_pattern_tokens(pattern: &Node<PatternJson>) -> syn::Result<TokenStream> {
+    let tokens = pattern_tokens(pattern)?;
+    Ok(match pattern.kind {
+        // the builder drops the parentheses that `x @ (A | B)` needs
+        PatternJson::Or { .. } => quote!((#tokens)),
+        _ => tokens,
+    })
+}
+
+/// referenced_pattern_tokens
+///
+/// # Arguments
+/// * `pattern`: &Node<PatternJson> - the pattern after `&` or `&mut`
+///
+/// # Returns
+/// * `syn::Result<TokenStream>` - the pattern, in parentheses when `&` would bind differently without them
+fn referenced_pattern_tokens(pattern: &Node<PatternJson>) -> syn::Result<TokenStream> {
+    let tokens = pattern_tokens(pattern)?;
+    Ok(match &pattern.kind {
+        // `&(mut x)` is not `&mut x`, and `&(0..=9)` is not a range starting at `&0`
+        PatternJson::Range { .. } | PatternJson::Or { .. } => quote!((#tokens)),
+        PatternJson::Identifier {
+            mutable,
+            subpattern,
+            ..
+        } if *mutable || subpattern.is_some() => quote!((#tokens)),
+        _ => tokens,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rust_source;
+    use std::fs;
+    use std::path::Path;
+
+    /// the model of a syntax tree with every span removed, for comparing two models
+    fn model_without_spans(file: &File) -> Value {
+        without_spans(&AstJson::from_file(file))
+    }
+
+    fn assert_round_trip(name: &str, source: &str) {
+        let ast = parse_rust_source(source).unwrap();
+        let json = serde_json::to_string(&AstJson::from_file(&ast)).unwrap();
+        let model: AstJson = serde_json::from_str(&json).unwrap();
+
+        // prettyplease may add braces around a match arm, so the models are compared
+        // before formatting and the formatted source only has to parse
+        let file = model
+            .to_file()
+            .unwrap_or_else(|err| panic!("{}: {}", name, err));
+        assert_eq!(
+            model_without_spans(&file),
+            model_without_spans(&ast),
+            "{} changed in the round trip",
+            name
+        );
+        // the model leaves out some details, such as attributes it does not know about, so the
+        // two files are also compared after formatting, which ignores trailing commas
+        assert_eq!(
+            prettyplease::unparse(&file),
+            prettyplease::unparse(&ast),
+            "{} lost code in the round trip",
+            name
+        );
+        let generated = model.to_source().unwrap();
+        assert!(parse_rust_source(&generated).is_ok(), "{}", generated);
+    }
+
+    /// round-trips every Rust file under `dir`, dropping the spans of each file afterwards
+    fn assert_round_trip_dir(dir: &Path) {
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    let source = fs::read_to_string(&path).unwrap();
+                    assert_round_trip(&path.display().to_string(), &source);
+                    // spans are recorded per thread, so drop them before the next file
+                    proc_macro2::extra::invalidate_current_thread_spans();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_source() {
+        let source = r#"
+            //! A crate
+            #![allow(dead_code)]
+
+            /// A point
+            #[derive(Debug)]
+            pub struct Point<T: Copy = f64>(pub T, T) where T: Default;
+
+            pub(crate) enum Shape {
+                Circle { radius: f64 },
+                Square(f64),
+                Empty = 3,
+            }
+
+            unsafe extern "C" fn add(a: i32, b: i32, ...) -> i32 {
+                let (x,) = (a,);
+                match x {
+                    0 | 1 if b > 0 => x + b,
+                    _ => loop {
+                        break 'outer;
+                    },
+                }
+            }
+
+            foo!(bar);
+        "#;
+
+        let ast = parse_rust_source(source).unwrap();
+        let generated = AstJson::from_file(&ast).to_source().unwrap();
+        assert!(generated.starts_with("//! A crate\n#![allow(dead_code)]\n"));
+        assert!(
+            generated.contains("/// A point\n#[derive(Debug)]\npub struct Point<T: Copy = f64>(")
+        );
+        assert!(generated.contains("where\n    T: Default;"));
+        assert!(generated.contains("unsafe extern \"C\" fn add(a: i32, b: i32, ...) -> i32 {"));
+        assert!(generated.contains("let (x,) = (a,);"));
+        assert!(generated.contains("foo!(bar);"));
+
+        assert_round_trip("test_to_source", source);
+    }
+
+    #[test]
+    fn test_docs_round_trip_verbatim() {
+        let source = "#[derive(Debug)]\n/// First\n#[allow(dead_code)]\n///no space\nstruct A;\nmod m {\n    //! inner\n}\n";
+
+        let ast = parse_rust_source(source).unwrap();
+        let json = serde_json::to_string(&AstJson::from_file(&ast)).unwrap();
+        let model: AstJson = serde_json::from_str(&json).unwrap();
+        assert_eq!(model.to_source().unwrap(), source);
+
+        // a model without doc attributes, such as one written by hand, still uses `docs`
+        let model: AstJson = serde_json::from_str(
+            r#"{"items": [{"type": "Struct", "name": "A", "kind": "unit", "fields": [], "docs": "A"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(model.to_source().unwrap(), "/// A\nstruct A;\n");
+    }
+
+    #[test]
+    fn test_edits_to_one_of_two_views_are_reported() {
+        let source = "/// A\nconst C: u8 = 1;\n";
+        let mut model = AstJson::from_file(&parse_rust_source(source).unwrap());
+
+        // `docs` and the doc attributes describe the same comments, so both must be edited
+        model.items[0].docs = Some("B".to_string());
+        let err = model.to_source().unwrap_err();
+        assert!(err.to_string().contains("`docs`"), "{}", err);
+        model.items[0].attributes[0].doc = Some(" B".to_string());
+        assert_eq!(model.to_source().unwrap(), "/// B\nconst C: u8 = 1;\n");
+
+        // the same holds for a type as written and its structure
+        let ItemJson::Const { type_info, .. } = &mut model.items[0].kind else {
+            panic!("expected a const");
+        };
+        *type_info = "u16".to_string();
+        let err = model.to_source().unwrap_err();
+        assert!(err.to_string().contains("`u16`"), "{}", err);
+
+        let ItemJson::Const { type_tree, .. } = &mut model.items[0].kind else {
+            panic!("expected a const");
+        };
+        *type_tree = build_type(&syn::parse_quote!(u16));
+        assert_eq!(model.to_source().unwrap(), "/// B\nconst C: u16 = 1;\n");
+    }
+
+    #[test]
+    fn test_other_nodes_take_attributes_from_the_model() {
+        let source = "#[cfg(x)] trait A = B; fn f() { #[allow(x)] foo!(); }";
+
+        let mut model = AstJson::from_file(&parse_rust_source(source).unwrap());
+        assert_eq!(
+            model.items[0].kind,
+            ItemJson::Other {
+                description: "trait A = B ;".to_string()
+            }
+        );
+
+        // editing the attributes changes the generated code
+        model.items[0].attributes[0].arguments = Some("y".to_string());
+        let generated = model.to_source().unwrap();
+        assert!(generated.contains("#[cfg(y)]\ntrait A = B;"));
+        assert!(generated.contains("#[allow(x)]\n    foo!();"));
+        assert_eq!(generated.matches("#[").count(), 2);
+    }
+
+    #[test]
+    fn test_parenthesized_patterns_round_trip() {
+        let source = r#"
+            fn f(v: &u8, w: &mut Option<u8>) {
+                let &(mut x) = v;
+                let &mut (ref y @ Some(_)) = w;
+                match v {
+                    &(0..=9) | &(b'a'..) => {}
+                    &(1 | 2) => {}
+                    &x => {}
+                }
+            }
+        "#;
+
+        let generated = AstJson::from_file(&parse_rust_source(source).unwrap())
+            .to_source()
+            .unwrap();
+        assert!(generated.contains("let &(mut x) = v;"));
+        assert!(generated.contains("let &mut (ref y @ Some(_)) = w;"));
+        assert!(generated.contains("&(0..=9) | &(b'a'..) => {}"));
+        assert!(generated.contains("&(1 | 2) => {}"));
+        assert!(generated.contains("&x => {}"));
+
+        assert_round_trip("test_parenthesized_patterns_round_trip", source);
+    }
+
+    #[test]
+    fn test_closure_and_impl_qualifiers_round_trip() {
+        let source = r#"
+            default unsafe impl<T> Send for Wrapper<T> {}
+            fn f() {
+                let a = for<'a> |x: &'a u8| -> &'a u8 { x };
+                let b = const || 1;
+                let c = static move || {};
+            }
+        "#;
+
+        let generated = AstJson::from_file(&parse_rust_source(source).unwrap())
+            .to_source()
+            .unwrap();
+        assert!(generated.contains("default unsafe impl<T> Send for Wrapper<T> {}"));
+        assert!(generated.contains("let a = for<'a> |x: &'a u8| -> &'a u8 { x };"));
+        assert!(generated.contains("let b = const || 1;"));
+        assert!(generated.contains("let c = static move || {};"));
+
+        assert_round_trip("test_closure_and_impl_qualifiers_round_trip", source);
+    }
+
+    #[test]
+    fn test_round_trip_over_crate_sources() {
+        assert_round_trip_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"));
+    }
+
+    #[test]
+    fn test_round_trip_over_third_party_sources() {
+        assert_round_trip_dir(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/round_trip"),
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use syn::parse::{ParseStream, Parser};
+use syn::{Attribute, Block, Expr, Item, Pat, Stmt, Type};
+
+use crate::attribute::build_attributes;
+use crate::builder::{build_expr, build_item, build_pattern, build_stmt, build_type};
+use crate::model::FragmentJson;
+use crate::pretty::pretty_tokens;
+
+/// The syntax a piece of Rust source is parsed as
+///
+/// # Variants
+/// * `Expr` - an expression, as in `a + b * c`
+/// * `Type` - a type, as in `Vec<&'a str>`
+/// * `Item` - an item, as in `struct Point { x: f64 }`
+/// * `Stmt` - a statement, as in `let x = 1;`
+/// * `Pat` - a pattern, as in `Some((a, _)) | None`
+/// * `Block` - the statements of a block without the braces, as in `let x = 1; x + 1`
+/// * `Attr` - attributes and doc comments, as in `#[derive(Debug)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentKind {
+    Expr,
+    Type,
+    Item,
+    Stmt,
+    Pat,
+    Block,
+    Attr,
+}
+
+/// parse a fragment of rust source code
+///
+/// # Arguments
+/// * `source`: &str - rust source code smaller than a file
+/// * `kind`: FragmentKind - the syntax to parse the source as
+///
+/// # Returns
+/// * `Result<FragmentJson, syn::Error>` - the model of the fragment
+///
+/// # Errors
+/// * `syn::Error` - parse error
+pub fn parse_rust_fragment(source: &str, kind: FragmentKind) -> Result<FragmentJson, syn::Error> {
+    Ok(match kind {
+        FragmentKind::Expr => FragmentJson::Expression {
+            expr: build_expr(&syn::parse_str::<Expr>(source)?),
+        },
+        FragmentKind::Type => {
+            let ty = syn::parse_str::<Type>(source)?;
+            FragmentJson::Type {
+                type_info: pretty_tokens(&ty),
+                type_tree: build_type(&ty),
+            }
+        }
+        FragmentKind::Item => FragmentJson::Item {
+            item: build_item(&syn::parse_str::<Item>(source)?),
+        },
+        FragmentKind::Stmt => FragmentJson::Statement {
+            stmt: build_stmt(&syn::parse_str::<Stmt>(source)?),
+        },
+        FragmentKind::Pat => FragmentJson::Pattern {
+            pattern: build_pattern(&Pat::parse_multi_with_leading_vert.parse_str(source)?),
+        },
+        FragmentKind::Block => FragmentJson::Block {
+            body: Block::parse_within
+                .parse_str(source)?
+                .iter()
+                .map(build_stmt)
+                .collect(),
+        },
+        FragmentKind::Attr => {
+            let (attributes, docs) = build_attributes(&parse_attributes.parse_str(source)?);
+            FragmentJson::Attributes { attributes, docs }
+        }
+    })
+}
+
+/// parse_attributes
+///
+/// # Arguments
+/// * `input`: ParseStream - the tokens of the fragment
+///
+/// # Returns
+/// * `syn::Result<Vec<Attribute>>` - the inner attributes followed by the outer ones
+fn parse_attributes(input: ParseStream) -> syn::Result<Vec<Attribute>> {
+    let mut attrs = Attribute::parse_inner(input)?;
+    attrs.extend(Attribute::parse_outer(input)?);
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ExprJson, PatternJson, StmtJson};
+
+    #[test]
+    fn test_parse_expression_fragment() {
+        let fragment = parse_rust_fragment("a + b * c", FragmentKind::Expr).unwrap();
+        let FragmentJson::Expression { expr } = fragment else {
+            panic!("expected an expression");
+        };
+        let ExprJson::Binary {
+            operator, right, ..
+        } = expr.kind
+        else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(operator, "+");
+        assert!(matches!(right.kind, ExprJson::Binary { ref operator, .. } if operator == "*"));
+    }
+
+    #[test]
+    fn test_parse_other_fragments() {
+        let fragment = parse_rust_fragment("Vec < & 'a str >", FragmentKind::Type).unwrap();
+        assert!(
+            matches!(fragment, FragmentJson::Type { ref type_info, .. } if type_info == "Vec<&'a str>")
+        );
+
+        let fragment = parse_rust_fragment("| Some(x) | None", FragmentKind::Pat).unwrap();
+        assert!(matches!(
+            fragment,
+            FragmentJson::Pattern { ref pattern } if matches!(pattern.kind, PatternJson::Or { .. })
+        ));
+
+        let fragment = parse_rust_fragment("let x = 1; x + 1", FragmentKind::Block).unwrap();
+        let FragmentJson::Block { body } = fragment else {
+            panic!("expected a block");
+        };
+        assert!(matches!(body[0].kind, StmtJson::VariableDeclaration { .. }));
+        assert!(matches!(
+            body[1].kind,
+            StmtJson::Expression {
+                semicolon: false,
+                ..
+            }
+        ));
+
+        let fragment =
+            parse_rust_fragment("/// A point\n#[derive(Debug)]", FragmentKind::Attr).unwrap();
+        let FragmentJson::Attributes { attributes, docs } = fragment else {
+            panic!("expected attributes");
+        };
+        assert_eq!(docs.as_deref(), Some("A point"));
+        assert_eq!(attributes[0].doc.as_deref(), Some(" A point"));
+        assert_eq!(attributes[1].derives, vec!["Debug"]);
+
+        assert!(parse_rust_fragment("fn (", FragmentKind::Item).is_err());
+    }
+}
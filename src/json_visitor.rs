@@ -1,135 +1,6 @@
-use quote::ToTokens;
-use serde::Serialize;
-use syn::{Expr, File, Item, Lit, Pat, Stmt, visit::Visit};
+use syn::{File, visit::Visit};
 
-/// A serializable representation of a Rust AST for JSON output
-///
-/// # Fields
-/// * `items`: Vec<ItemJson> - the items in the AST
-#[derive(Serialize, Debug, Default)]
-pub struct AstJson {
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub items: Vec<ItemJson>,
-}
-
-/// # Fields
-/// * `name`: String - the name of the item
-/// * `parameters`: Vec<ParameterJson> - the parameters of the item
-/// * `return_type`: Option<String> - the return type of the item
-/// * `body`: Vec<StmtJson> - the body of the item
-#[derive(Serialize, Debug)]
-#[serde(tag = "type")]
-pub enum ItemJson {
-    Function {
-        name: String,
-        parameters: Vec<ParameterJson>,
-        return_type: Option<String>,
-        body: Vec<StmtJson>,
-    },
-    Struct {
-        name: String,
-        fields: Vec<FieldJson>,
-    },
-    Enum {
-        name: String,
-        variants: Vec<VariantJson>,
-    },
-    Other {
-        description: String,
-    },
-}
-
-/// # Fields
-/// * `name`: String - the name of the parameter
-/// * `type_info`: String - the type of the parameter
-#[derive(Serialize, Debug)]
-pub struct ParameterJson {
-    pub name: String,
-    pub type_info: String,
-}
-
-/// # Fields
-/// * `name`: Option<String> - the name of the field
-/// * `type_info`: String - the type of the field
-#[derive(Serialize, Debug)]
-pub struct FieldJson {
-    pub name: Option<String>,
-    pub type_info: String,
-}
-
-/// # Fields
-/// * `name`: String - the name of the variant
-#[derive(Serialize, Debug)]
-pub struct VariantJson {
-    pub name: String,
-}
-
-/// # Fields
-/// * `name`: String - the name of the statement
-/// * `initializer`: Option<Box<ExprJson>> - the initializer of the statement
-#[derive(Serialize, Debug)]
-#[serde(tag = "type")]
-pub enum StmtJson {
-    VariableDeclaration {
-        name: String,
-        initializer: Option<Box<ExprJson>>,
-    },
-    Expression {
-        expr: Box<ExprJson>,
-    },
-    Other {
-        description: String,
-    },
-}
-
-/// # Fields
-/// * `value`: String - the value of the literal
-#[derive(Serialize, Debug)]
-#[serde(tag = "type")]
-pub enum ExprJson {
-    IntLiteral {
-        value: String,
-    },
-    FloatLiteral {
-        value: String,
-    },
-    StringLiteral {
-        value: String,
-    },
-    BoolLiteral {
-        value: bool,
-    },
-    Binary {
-        operator: String,
-        left: Box<ExprJson>,
-        right: Box<ExprJson>,
-    },
-    FunctionCall {
-        function: Box<ExprJson>,
-        arguments: Vec<ExprJson>,
-    },
-    Identifier {
-        name: String,
-    },
-    If {
-        condition: Box<ExprJson>,
-        then_branch: Vec<StmtJson>,
-        else_branch: Option<Box<ExprJson>>,
-    },
-    Loop {
-        body: Vec<StmtJson>,
-    },
-    While {
-        condition: Box<ExprJson>,
-        body: Vec<StmtJson>,
-    },
-    Return {
-        value: Option<Box<ExprJson>>,
-    },
-    Other {
-        description: String,
-    },
-}
+use crate::model::AstJson;
 
 /// A visitor that builds a JSON representation of a Rust AST
 ///
@@ -143,7 +14,6 @@ pub struct JsonVisitor {
 /// * `new()`: creates a new JsonVisitor
 /// * `to_json()`: converts the AST to a JSON string
 /// * `process_file()`: processes a file and adds its items to the AST
-/// * `process_item()`: processes an item and adds it to the AST
 impl Default for JsonVisitor {
     fn default() -> Self {
         Self::new()
@@ -187,94 +57,13 @@ impl JsonVisitor {
     /// # Returns
     /// * `()`
     pub fn process_file(&mut self, file: &File) {
-        for item in &file.items {
-            self.process_item(item);
-        }
-    }
-
-    /// process_item
-    ///
-    /// # Arguments
-    /// * `self`: &mut Self - the JsonVisitor
-    /// * `item`: &Item - the item to process
-    ///
-    /// # Returns
-    /// * `()`
-    fn process_item(&mut self, item: &Item) {
-        match item {
-            Item::Fn(item_fn) => {
-                let mut parameters = Vec::new();
-                for param in &item_fn.sig.inputs {
-                    match param {
-                        syn::FnArg::Typed(pat_type) => {
-                            if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                                parameters.push(ParameterJson {
-                                    name: pat_ident.ident.to_string(),
-                                    type_info: format!("{}", (*pat_type.ty).to_token_stream()),
-                                });
-                            }
-                        }
-                        syn::FnArg::Receiver(receiver) => {
-                            parameters.push(ParameterJson {
-                                name: "self".to_string(),
-                                type_info: format!("{}", receiver.to_token_stream()),
-                            });
-                        }
-                    }
-                }
-
-                let return_type = match &item_fn.sig.output {
-                    syn::ReturnType::Default => None,
-                    syn::ReturnType::Type(_, return_type) => {
-                        Some(format!("{}", return_type.to_token_stream()))
-                    }
-                };
-
-                let mut statements = Vec::new();
-                for stmt in &item_fn.block.stmts {
-                    statements.push(self.visit_stmt_json(stmt));
-                }
-
-                self.ast.items.push(ItemJson::Function {
-                    name: item_fn.sig.ident.to_string(),
-                    parameters,
-                    return_type,
-                    body: statements,
-                });
-            }
-            Item::Struct(item_struct) => {
-                let mut fields = Vec::new();
-                for field in &item_struct.fields {
-                    fields.push(FieldJson {
-                        name: field.ident.as_ref().map(|ident| ident.to_string()),
-                        type_info: format!("{}", field.ty.to_token_stream()),
-                    });
-                }
-
-                self.ast.items.push(ItemJson::Struct {
-                    name: item_struct.ident.to_string(),
-                    fields,
-                });
-            }
-            Item::Enum(item_enum) => {
-                let mut variants = Vec::new();
-                for variant in &item_enum.variants {
-                    variants.push(VariantJson {
-                        name: variant.ident.to_string(),
-                    });
-                }
-
-                self.ast.items.push(ItemJson::Enum {
-                    name: item_enum.ident.to_string(),
-                    variants,
-                });
-            }
-            _ => {
-                self.ast.items.push(ItemJson::Other {
-                    description: format!("{}", item.to_token_stream()),
-                });
-            }
-        }
+        let ast = AstJson::from_file(file);
+        self.ast.attributes.extend(ast.attributes);
+        self.ast.docs = match (self.ast.docs.take(), ast.docs) {
+            (Some(docs), Some(more)) => Some(format!("{}\n{}", docs, more)),
+            (docs, more) => docs.or(more),
+        };
+        self.ast.items.extend(ast.items);
     }
 }
 
@@ -294,150 +83,10 @@ impl<'ast> Visit<'ast> for JsonVisitor {
     }
 }
 
-/// # Implementations
-/// * `Visit<'ast>` - the Visit trait
-impl JsonVisitor {
-    /// visit_stmt_json
-    ///
-    /// # Arguments
-    /// * `self`: &mut Self - the JsonVisitor
-    /// * `stmt`: &Stmt - the statement to process
-    ///
-    /// # Returns
-    fn visit_stmt_json(&mut self, stmt: &Stmt) -> StmtJson {
-        match stmt {
-            Stmt::Local(local) => {
-                let name = if let Pat::Ident(pat_ident) = &local.pat {
-                    pat_ident.ident.to_string()
-                } else {
-                    "unknown".to_string()
-                };
-
-                let initializer = local.init.as_ref().map(|init| Box::new(self.visit_expr_json(&init.expr)));
-
-                StmtJson::VariableDeclaration { name, initializer }
-            }
-            Stmt::Expr(expr, _) => StmtJson::Expression {
-                expr: Box::new(self.visit_expr_json(expr)),
-            },
-            _ => StmtJson::Other {
-                description: format!("{}", stmt.to_token_stream()),
-            },
-        }
-    }
-
-    /// visit_expr_json
-    ///
-    /// # Arguments
-    /// * `self`: &mut Self - the JsonVisitor
-    /// * `expr`: &Expr - the expression to process
-    ///
-    /// # Returns
-    /// * `ExprJson` - the JSON representation of the expression
-    fn visit_expr_json(&mut self, expr: &Expr) -> ExprJson {
-        match expr {
-            Expr::Lit(expr_lit) => match &expr_lit.lit {
-                Lit::Int(lit_int) => ExprJson::IntLiteral {
-                    value: lit_int.base10_digits().to_string(),
-                },
-                Lit::Float(lit_float) => ExprJson::FloatLiteral {
-                    value: lit_float.base10_digits().to_string(),
-                },
-                Lit::Str(lit_str) => ExprJson::StringLiteral {
-                    value: lit_str.value(),
-                },
-                Lit::Bool(lit_bool) => ExprJson::BoolLiteral {
-                    value: lit_bool.value,
-                },
-                _ => ExprJson::Other {
-                    description: format!("{}", expr_lit.to_token_stream()),
-                },
-            },
-            Expr::Binary(expr_bin) => {
-                let op = match expr_bin.op {
-                    syn::BinOp::Add(_) => "+",
-                    syn::BinOp::Sub(_) => "-",
-                    syn::BinOp::Mul(_) => "*",
-                    syn::BinOp::Div(_) => "/",
-                    syn::BinOp::Eq(_) => "==",
-                    syn::BinOp::Lt(_) => "<",
-                    syn::BinOp::Le(_) => "<=",
-                    syn::BinOp::Ne(_) => "!=",
-                    syn::BinOp::Ge(_) => ">=",
-                    syn::BinOp::Gt(_) => ">",
-                    _ => "other_operator",
-                };
-
-                ExprJson::Binary {
-                    operator: op.to_string(),
-                    left: Box::new(self.visit_expr_json(&expr_bin.left)),
-                    right: Box::new(self.visit_expr_json(&expr_bin.right)),
-                }
-            }
-            Expr::Call(expr_call) => ExprJson::FunctionCall {
-                function: Box::new(self.visit_expr_json(&expr_call.func)),
-                arguments: expr_call
-                    .args
-                    .iter()
-                    .map(|arg| self.visit_expr_json(arg))
-                    .collect(),
-            },
-            Expr::Path(expr_path) => ExprJson::Identifier {
-                name: format!("{}", expr_path.to_token_stream()),
-            },
-            Expr::If(expr_if) => {
-                let mut then_stmts = Vec::new();
-                for stmt in &expr_if.then_branch.stmts {
-                    then_stmts.push(self.visit_stmt_json(stmt));
-                }
-
-                let else_branch = if let Some((_, else_expr)) = &expr_if.else_branch {
-                    Some(Box::new(self.visit_expr_json(else_expr)))
-                } else {
-                    None
-                };
-
-                ExprJson::If {
-                    condition: Box::new(self.visit_expr_json(&expr_if.cond)),
-                    then_branch: then_stmts,
-                    else_branch,
-                }
-            }
-            Expr::Loop(expr_loop) => {
-                let mut stmts = Vec::new();
-                for stmt in &expr_loop.body.stmts {
-                    stmts.push(self.visit_stmt_json(stmt));
-                }
-
-                ExprJson::Loop { body: stmts }
-            }
-            Expr::While(expr_while) => {
-                let mut stmts = Vec::new();
-                for stmt in &expr_while.body.stmts {
-                    stmts.push(self.visit_stmt_json(stmt));
-                }
-
-                ExprJson::While {
-                    condition: Box::new(self.visit_expr_json(&expr_while.cond)),
-                    body: stmts,
-                }
-            }
-            Expr::Return(expr_return) => ExprJson::Return {
-                value: expr_return
-                    .expr
-                    .as_ref()
-                    .map(|e| Box::new(self.visit_expr_json(e))),
-            },
-            _ => ExprJson::Other {
-                description: format!("{}", expr.to_token_stream()),
-            },
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{ItemJson, Node};
     use crate::parse_rust_source;
     use serde_json::Value;
 
@@ -631,12 +280,21 @@ mod tests {
         let mut ast = AstJson::default();
 
         // 関数アイテムを追加
-        ast.items.push(ItemJson::Function {
+        ast.items.push(Node::new(ItemJson::Function {
             name: "manual_func".to_string(),
+            visibility: None,
+            is_const: false,
+            is_async: false,
+            is_unsafe: false,
+            is_extern: false,
+            abi: None,
+            generics: Default::default(),
             parameters: vec![],
+            variadic: false,
             return_type: Some("i32".to_string()),
-            body: vec![],
-        });
+            return_type_tree: None,
+            body: Some(vec![]),
+        }));
 
         // JSONシリアライズ
         let json = serde_json::to_string_pretty(&ast).unwrap();
@@ -654,4 +312,874 @@ mod tests {
         assert_eq!(first_item["type"], "Function");
         assert_eq!(first_item["name"], "manual_func");
     }
+
+    // 位置情報のテスト
+    #[test]
+    fn test_json_spans() {
+        let source = "fn add(a: i32) -> i32 {\n    a + 1\n}\n";
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let first_item = &parsed["items"][0];
+
+        // 関数全体の範囲を確認
+        assert_eq!(first_item["span"]["start"]["line"], 1);
+        assert_eq!(first_item["span"]["start"]["column"], 0);
+        assert_eq!(first_item["span"]["start"]["offset"], 0);
+        assert_eq!(first_item["span"]["end"]["line"], 3);
+        assert_eq!(first_item["span"]["end"]["offset"], source.trim_end().len());
+
+        // パラメータの範囲を確認
+        assert_eq!(first_item["parameters"][0]["span"]["start"]["column"], 7);
+
+        // 式の範囲を確認
+        let expr = &first_item["body"][0]["expr"];
+        assert_eq!(expr["span"]["start"]["line"], 2);
+        assert_eq!(expr["span"]["start"]["column"], 4);
+        assert_eq!(expr["span"]["start"]["offset"], 28);
+        assert_eq!(expr["span"]["end"]["offset"], 33);
+        assert_eq!(expr["right"]["span"]["start"]["column"], 8);
+    }
+
+    #[test]
+    fn test_json_serialization_impl_and_trait() {
+        let source = r#"
+            trait Shape: Clone + Debug {
+                type Unit: Copy;
+                const SIDES: u32;
+                fn area(&self) -> f64;
+                fn describe(&self) -> String {
+                    format!("{} sides", Self::SIDES)
+                }
+            }
+
+            unsafe impl<T: Copy> Shape for Square<T> where T: Debug {
+                type Unit = T;
+                const SIDES: u32 = 4;
+                fn area(&self) -> f64 {
+                    self.side * self.side
+                }
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+
+        // トレイトを確認
+        let shape = &parsed["items"][0];
+        assert_eq!(shape["type"], "Trait");
+        assert_eq!(shape["name"], "Shape");
+        assert_eq!(shape["supertraits"][0], "Clone");
+        assert_eq!(shape["supertraits"][1], "Debug");
+        assert_eq!(shape["items"][0]["type"], "TypeAlias");
+        assert_eq!(shape["items"][0]["bounds"][0], "Copy");
+        assert_eq!(shape["items"][1]["type"], "Const");
+        assert!(shape["items"][1]["value"].is_null());
+
+        // 必須メソッドには本体がなく、既定メソッドには本体がある
+        assert_eq!(shape["items"][2]["name"], "area");
+        assert!(shape["items"][2].get("body").is_none());
+        assert_eq!(shape["items"][3]["name"], "describe");
+        assert!(shape["items"][3]["body"].is_array());
+
+        // implを確認
+        let square = &parsed["items"][1];
+        assert_eq!(square["type"], "Impl");
+        assert_eq!(square["is_unsafe"], true);
+        assert_eq!(square["trait_name"], "Shape");
+        assert_eq!(square["self_type"], "Square<T>");
+        assert_eq!(square["generics"]["params"][0]["kind"], "Type");
+        assert_eq!(square["generics"]["params"][0]["name"], "T");
+        assert_eq!(square["generics"]["params"][0]["bounds"][0], "Copy");
+        assert_eq!(square["generics"]["where_clause"][0]["bounded_type"], "T");
+
+        // メソッド本体まで含まれる
+        assert_eq!(square["items"][2]["type"], "Function");
+        assert_eq!(square["items"][2]["body"][0]["expr"]["type"], "Binary");
+    }
+
+    #[test]
+    fn test_json_serialization_modules_and_imports() {
+        let source = r#"
+            extern crate alloc as heap;
+            use std::{fmt::{self, Display as Show}, io::*};
+            mod inline {
+                mod nested {
+                    fn deep() {}
+                }
+            }
+            mod declared;
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // extern crateを確認
+        assert_eq!(items[0]["type"], "ExternCrate");
+        assert_eq!(items[0]["name"], "alloc");
+        assert_eq!(items[0]["alias"], "heap");
+
+        // useツリーは個別のパスに展開される
+        let paths = &items[1]["paths"];
+        assert_eq!(items[1]["type"], "Use");
+        assert_eq!(
+            items[1]["tree"],
+            "std::{fmt::{self, Display as Show}, io::*}"
+        );
+        assert_eq!(paths[0]["path"], "std::fmt");
+        assert!(paths[0]["alias"].is_null());
+        assert_eq!(paths[1]["path"], "std::fmt::Display");
+        assert_eq!(paths[1]["alias"], "Show");
+        assert_eq!(paths[2]["path"], "std::io");
+        assert_eq!(paths[2]["glob"], true);
+
+        // インラインモジュールは再帰的に展開される
+        assert_eq!(items[2]["type"], "Module");
+        assert_eq!(items[2]["inline"], true);
+        assert_eq!(items[2]["items"][0]["name"], "nested");
+        assert_eq!(items[2]["items"][0]["items"][0]["name"], "deep");
+
+        // 宣言だけのモジュール
+        assert_eq!(items[3]["name"], "declared");
+        assert_eq!(items[3]["inline"], false);
+        assert!(items[3]["items"].is_null());
+    }
+
+    #[test]
+    fn test_json_serialization_value_items() {
+        let source = r#"
+            const MAX: usize = 10;
+            static mut COUNTER: u32 = 0;
+            type Map<K> = std::collections::HashMap<K, String>;
+            union Bits { int: u32, float: f32 }
+            macro_rules! square {
+                ($x:expr) => { $x * $x };
+                () => { 0 };
+            }
+            extern "C" {
+                fn abs(x: i32) -> i32;
+                static errno: i32;
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // const / static
+        assert_eq!(items[0]["type"], "Const");
+        assert_eq!(items[0]["type_info"], "usize");
+        assert_eq!(items[0]["value"]["value"], "10");
+        assert_eq!(items[1]["type"], "Static");
+        assert_eq!(items[1]["mutable"], true);
+
+        // 型エイリアス
+        assert_eq!(items[2]["type"], "TypeAlias");
+        assert_eq!(items[2]["generics"]["params"][0]["name"], "K");
+
+        // union
+        assert_eq!(items[3]["type"], "Union");
+        assert_eq!(items[3]["fields"][1]["name"], "float");
+
+        // macro_rules! はルールごとに分解される
+        assert_eq!(items[4]["type"], "MacroRules");
+        assert_eq!(items[4]["name"], "square");
+        assert_eq!(items[4]["rules"][0]["matcher"], "($ x : expr)");
+        assert_eq!(items[4]["rules"][0]["transcriber"], "{ $ x * $ x }");
+        assert_eq!(items[4]["rules"][1]["matcher"], "()");
+
+        // extern ブロック
+        assert_eq!(items[5]["type"], "ExternBlock");
+        assert_eq!(items[5]["abi"], "C");
+        assert_eq!(items[5]["items"][0]["type"], "Function");
+        assert!(items[5]["items"][0].get("body").is_none());
+        assert_eq!(items[5]["items"][1]["type"], "Static");
+        assert_eq!(items[5]["items"][1]["mutable"], false);
+    }
+
+    #[test]
+    fn test_json_serialization_expressions() {
+        let source = r#"
+            fn exprs(v: &mut Vec<u8>, p: Point) {
+                let n = match v.len() {
+                    0 => -1,
+                    n if n > 10 => 10,
+                    _ => *v.first().unwrap() as i32,
+                };
+                let f = move |x: u8, y| x + y;
+                v[0] += p.x;
+                let r = &mut v;
+                total = { p.0 };
+                v.iter().collect::<Vec<_>>();
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let body = &parsed["items"][0]["body"];
+
+        // match式とアーム
+        let matched = &body[0]["initializer"];
+        assert_eq!(matched["type"], "Match");
+        assert_eq!(matched["expr"]["type"], "MethodCall");
+        assert_eq!(matched["expr"]["method"], "len");
+        assert_eq!(matched["arms"][0]["pattern"]["type"], "Literal");
+        assert_eq!(matched["arms"][0]["pattern"]["value"]["value"], "0");
+        assert_eq!(matched["arms"][0]["body"]["type"], "Unary");
+        assert_eq!(matched["arms"][0]["body"]["operator"], "-");
+        assert_eq!(matched["arms"][1]["guard"]["type"], "Binary");
+        assert!(matched["arms"][2]["guard"].is_null());
+        assert_eq!(matched["arms"][2]["body"]["type"], "Cast");
+        assert_eq!(matched["arms"][2]["body"]["type_info"], "i32");
+        assert_eq!(matched["arms"][2]["body"]["expr"]["operator"], "*");
+
+        // クロージャ
+        let closure = &body[1]["initializer"];
+        assert_eq!(closure["type"], "Closure");
+        assert_eq!(closure["is_move"], true);
+        assert!(closure.get("is_async").is_none());
+        assert_eq!(closure["parameters"][0]["pattern"]["name"], "x");
+        assert_eq!(closure["parameters"][0]["type_info"], "u8");
+        assert!(closure["parameters"][1]["type_info"].is_null());
+        assert_eq!(closure["body"]["type"], "Binary");
+
+        // 複合代入・インデックス・フィールドアクセス
+        let compound = &body[2]["expr"];
+        assert_eq!(compound["type"], "CompoundAssign");
+        assert_eq!(compound["operator"], "+=");
+        assert_eq!(compound["target"]["type"], "Index");
+        assert_eq!(compound["value"]["type"], "FieldAccess");
+        assert_eq!(compound["value"]["member"], "x");
+
+        // 参照
+        assert_eq!(body[3]["initializer"]["type"], "Reference");
+        assert_eq!(body[3]["initializer"]["mutable"], true);
+
+        // 代入とブロック式
+        let assign = &body[4]["expr"];
+        assert_eq!(assign["type"], "Assign");
+        assert_eq!(assign["value"]["type"], "Block");
+        assert_eq!(assign["value"]["body"][0]["expr"]["member"], "0");
+
+        // ターボフィッシュ付きメソッド呼び出し
+        let collect = &body[5]["expr"];
+        assert_eq!(collect["method"], "collect");
+        assert_eq!(collect["turbofish"][0], "Vec<_>");
+        assert_eq!(collect["receiver"]["method"], "iter");
+    }
+
+    #[test]
+    fn test_json_serialization_control_flow() {
+        let source = r#"
+            async fn flow(items: Vec<Option<u8>>) -> Result<u8, E> {
+                let Some(first) = items.first() else { return Err(E) };
+                'outer: for item in items.iter() {
+                    while let Some(x) = item {
+                        if let Some(y) = x.checked_add(1) && y > 3 {
+                            continue 'outer;
+                        }
+                        break;
+                    }
+                }
+                let found = loop { break 42; };
+                let value = fetch().await?;
+                unsafe { touch() };
+                async move { value };
+                const { 1 };
+                Ok(value)
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let body = &parsed["items"][0]["body"];
+
+        // let-else の else ブロック
+        assert_eq!(body[0]["type"], "VariableDeclaration");
+        assert_eq!(body[0]["else_branch"]["type"], "Block");
+        assert_eq!(body[0]["else_branch"]["body"][0]["expr"]["type"], "Return");
+
+        // ラベル付き for ループ
+        let for_loop = &body[1]["expr"];
+        assert_eq!(for_loop["type"], "ForLoop");
+        assert_eq!(for_loop["label"], "'outer");
+        assert_eq!(for_loop["pattern"]["name"], "item");
+        assert_eq!(for_loop["iterable"]["type"], "MethodCall");
+
+        // while let と let チェーン
+        let while_let = &for_loop["body"][0]["expr"];
+        assert_eq!(while_let["type"], "While");
+        assert!(while_let.get("label").is_none());
+        assert_eq!(while_let["condition"]["type"], "Let");
+        assert_eq!(while_let["condition"]["pattern"]["type"], "TupleStruct");
+        assert_eq!(while_let["condition"]["pattern"]["path"], "Some");
+        let chain = &while_let["body"][0]["expr"]["condition"];
+        assert_eq!(chain["operator"], "&&");
+        assert_eq!(chain["left"]["type"], "Let");
+
+        // continue と break
+        let continue_expr = &while_let["body"][0]["expr"]["then_branch"][0]["expr"];
+        assert_eq!(continue_expr["type"], "Continue");
+        assert_eq!(continue_expr["label"], "'outer");
+        assert_eq!(while_let["body"][1]["expr"]["type"], "Break");
+        assert!(while_let["body"][1]["expr"]["value"].is_null());
+
+        // 値付き break
+        let break_expr = &body[2]["initializer"]["body"][0]["expr"];
+        assert_eq!(break_expr["type"], "Break");
+        assert_eq!(break_expr["value"]["value"], "42");
+
+        // ? と .await
+        let try_expr = &body[3]["initializer"];
+        assert_eq!(try_expr["type"], "Try");
+        assert_eq!(try_expr["expr"]["type"], "Await");
+        assert_eq!(try_expr["expr"]["expr"]["type"], "FunctionCall");
+
+        // unsafe / async / const ブロック
+        assert_eq!(body[4]["expr"]["type"], "UnsafeBlock");
+        assert_eq!(body[5]["expr"]["type"], "AsyncBlock");
+        assert_eq!(body[5]["expr"]["is_move"], true);
+        assert_eq!(body[6]["expr"]["type"], "ConstBlock");
+    }
+
+    #[test]
+    fn test_json_serialization_literals_and_aggregates() {
+        let source = r#"
+            fn values() {
+                let a = (0xFFu8, 2.5f32, 'x', b'y', b"bytes", c"cstr", r"raw");
+                let p = geo::Point { x: 1, y, ..Default::default() };
+                let arr = [1, 2, 3];
+                let zeros = [0; 4];
+                let r = (1..=10, ..5, 2..);
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let body = &parsed["items"][0]["body"];
+
+        // リテラルはサフィックスと元の表記を保持する
+        let literals = &body[0]["initializer"];
+        assert_eq!(literals["type"], "Tuple");
+        let elements = &literals["elements"];
+        assert_eq!(elements[0]["type"], "IntLiteral");
+        assert_eq!(elements[0]["value"], "255");
+        assert_eq!(elements[0]["suffix"], "u8");
+        assert_eq!(elements[0]["raw"], "0xFFu8");
+        assert_eq!(elements[1]["type"], "FloatLiteral");
+        assert_eq!(elements[1]["suffix"], "f32");
+        assert_eq!(elements[2]["type"], "CharLiteral");
+        assert_eq!(elements[2]["value"], "x");
+        assert_eq!(elements[3]["type"], "ByteLiteral");
+        assert_eq!(elements[3]["value"], 121);
+        assert_eq!(elements[4]["type"], "ByteStringLiteral");
+        assert_eq!(elements[4]["value"][0], 98);
+        assert_eq!(elements[5]["type"], "CStringLiteral");
+        assert_eq!(elements[5]["value"], "cstr");
+        assert_eq!(elements[6]["type"], "StringLiteral");
+        assert_eq!(elements[6]["value"], "raw");
+        assert_eq!(elements[6]["raw"], "r\"raw\"");
+
+        // 構造体リテラル
+        let point = &body[1]["initializer"];
+        assert_eq!(point["type"], "StructLiteral");
+        assert_eq!(point["path"], "geo::Point");
+        assert_eq!(point["fields"][0]["member"], "x");
+        assert!(point["fields"][0].get("shorthand").is_none());
+        assert_eq!(point["fields"][1]["member"], "y");
+        assert_eq!(point["fields"][1]["shorthand"], true);
+        assert_eq!(point["base"]["type"], "FunctionCall");
+        assert_eq!(point["base"]["function"]["name"], "Default::default");
+
+        // 配列
+        assert_eq!(body[2]["initializer"]["type"], "Array");
+        assert_eq!(body[2]["initializer"]["elements"][2]["value"], "3");
+        assert_eq!(body[3]["initializer"]["type"], "ArrayRepeat");
+        assert_eq!(body[3]["initializer"]["length"]["value"], "4");
+
+        // 範囲
+        let ranges = &body[4]["initializer"]["elements"];
+        assert_eq!(ranges[0]["type"], "Range");
+        assert_eq!(ranges[0]["inclusive"], true);
+        assert!(ranges[1]["start"].is_null());
+        assert!(ranges[1].get("inclusive").is_none());
+        assert!(ranges[2]["end"].is_null());
+    }
+
+    #[test]
+    fn test_json_serialization_operators() {
+        let source = r#"
+            fn ops(a: u32, b: u32, flag: bool) {
+                let c = a % b << 2 | a & b ^ 1;
+                let ok = !flag || a >= b;
+                let n = -(*&a as i64);
+                c >>= 1;
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let body = &parsed["items"][0]["body"];
+
+        // ビット演算と算術演算のカテゴリ
+        let bit_or = &body[0]["initializer"];
+        assert_eq!(bit_or["operator"], "|");
+        assert_eq!(bit_or["category"], "bitwise");
+        assert_eq!(bit_or["left"]["operator"], "<<");
+        assert_eq!(bit_or["left"]["left"]["operator"], "%");
+        assert_eq!(bit_or["left"]["left"]["category"], "arithmetic");
+        assert_eq!(bit_or["right"]["operator"], "^");
+        assert_eq!(bit_or["right"]["left"]["operator"], "&");
+
+        // 論理演算と比較演算
+        let or = &body[1]["initializer"];
+        assert_eq!(or["operator"], "||");
+        assert_eq!(or["category"], "logical");
+        assert_eq!(or["left"]["type"], "Unary");
+        assert_eq!(or["left"]["operator"], "!");
+        assert_eq!(or["right"]["category"], "comparison");
+
+        // 単項演算子
+        let neg = &body[2]["initializer"];
+        assert_eq!(neg["operator"], "-");
+        assert_eq!(neg["category"], "arithmetic");
+        let deref = &neg["operand"]["expr"]["expr"];
+        assert_eq!(deref["operator"], "*");
+        assert_eq!(deref["category"], "dereference");
+
+        // 複合代入
+        assert_eq!(body[3]["expr"]["type"], "CompoundAssign");
+        assert_eq!(body[3]["expr"]["operator"], ">>=");
+        assert_eq!(body[3]["expr"]["category"], "assignment");
+    }
+
+    #[test]
+    fn test_json_serialization_patterns() {
+        let source = r#"
+            fn patterns((a, b): (u8, u8), Point { x, y: ref mut py, .. }: Point) {
+                let (first, [head, .., tail]) = pair;
+                let value: u32 = 0;
+                match msg {
+                    Msg::Move { x: 0, .. } | Msg::Quit => {}
+                    Msg::Write(&mut ref text) => {}
+                    Msg::Code(n @ 1..=9) => {}
+                    _ => {}
+                }
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let function = &parsed["items"][0];
+
+        // 分解された引数も parameters に残る
+        let params = &function["parameters"];
+        assert_eq!(params[0]["name"], "(a, b)");
+        assert_eq!(params[0]["pattern"]["type"], "Tuple");
+        assert_eq!(params[0]["pattern"]["elements"][1]["name"], "b");
+        assert_eq!(params[0]["type_info"], "(u8, u8)");
+        let point = &params[1]["pattern"];
+        assert_eq!(point["type"], "Struct");
+        assert_eq!(point["has_rest"], true);
+        assert_eq!(point["fields"][0]["shorthand"], true);
+        assert_eq!(point["fields"][1]["member"], "y");
+        assert_eq!(point["fields"][1]["pattern"]["by_ref"], true);
+        assert_eq!(point["fields"][1]["pattern"]["mutable"], true);
+
+        // let の分解パターンでは name は出力されない
+        let body = &function["body"];
+        assert!(body[0].get("name").is_none());
+        let slice = &body[0]["pattern"]["elements"][1];
+        assert_eq!(slice["type"], "Slice");
+        assert_eq!(slice["elements"][1]["type"], "Rest");
+
+        // 型注釈付きの let
+        assert_eq!(body[1]["name"], "value");
+        assert_eq!(body[1]["type_info"], "u32");
+
+        // match アームのパターン
+        let arms = &body[2]["expr"]["arms"];
+        assert_eq!(arms[0]["pattern"]["type"], "Or");
+        assert_eq!(
+            arms[0]["pattern"]["cases"][0]["fields"][0]["pattern"]["type"],
+            "Literal"
+        );
+        assert_eq!(arms[0]["pattern"]["cases"][1]["type"], "Path");
+        let reference = &arms[1]["pattern"]["elements"][0];
+        assert_eq!(reference["type"], "Reference");
+        assert_eq!(reference["mutable"], true);
+        let binding = &arms[2]["pattern"]["elements"][0];
+        assert_eq!(binding["name"], "n");
+        assert_eq!(binding["subpattern"]["type"], "Range");
+        assert_eq!(binding["subpattern"]["inclusive"], true);
+        assert_eq!(arms[3]["pattern"]["type"], "Wildcard");
+    }
+
+    #[test]
+    fn test_json_serialization_types() {
+        let source = r#"
+            fn types<'a>(
+                names: &'a mut Vec<String>,
+                table: [u8; 4],
+                callback: unsafe extern "C" fn(i32, ...) -> !,
+                iter: impl Iterator<Item = u8> + 'a,
+                object: Box<dyn Fn(u8) -> bool + Send>,
+                raw: *const (),
+            ) -> <T as Trait>::Output {
+                let items: Vec<_> = collect();
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let function = &parsed["items"][0];
+        let params = &function["parameters"];
+
+        // 整形された文字列と型ツリーの両方が出力される
+        assert_eq!(params[0]["type_info"], "&'a mut Vec<String>");
+        let reference = &params[0]["type_tree"];
+        assert_eq!(reference["type"], "Reference");
+        assert_eq!(reference["lifetime"], "'a");
+        assert_eq!(reference["mutable"], true);
+        assert_eq!(reference["elem"]["segments"][0]["name"], "Vec");
+        let argument = &reference["elem"]["segments"][0]["arguments"][0];
+        assert_eq!(argument["kind"], "Type");
+        assert_eq!(argument["value"]["segments"][0]["name"], "String");
+
+        // 配列の長さは式として保持される
+        assert_eq!(params[1]["type_tree"]["type"], "Array");
+        assert_eq!(params[1]["type_tree"]["length"]["value"], "4");
+
+        // 関数ポインタ
+        let fn_pointer = &params[2]["type_tree"];
+        assert_eq!(fn_pointer["type"], "FnPointer");
+        assert_eq!(fn_pointer["is_unsafe"], true);
+        assert_eq!(fn_pointer["abi"], "C");
+        assert_eq!(fn_pointer["variadic"], true);
+        assert_eq!(fn_pointer["output"]["type"], "Never");
+
+        // impl Trait と dyn Trait
+        assert_eq!(params[3]["type_tree"]["type"], "ImplTrait");
+        assert_eq!(params[3]["type_tree"]["bounds"][0], "Iterator<Item = u8>");
+        let boxed = &params[4]["type_tree"]["segments"][0]["arguments"][0]["value"];
+        assert_eq!(boxed["type"], "TraitObject");
+        assert_eq!(boxed["bounds"][1], "Send");
+
+        // 生ポインタとユニット型
+        assert_eq!(params[5]["type_tree"]["type"], "Pointer");
+        assert_eq!(params[5]["type_tree"]["mutable"], false);
+        assert_eq!(params[5]["type_tree"]["elem"]["type"], "Tuple");
+
+        // 修飾パス
+        assert_eq!(function["return_type"], "<T as Trait>::Output");
+        assert_eq!(
+            function["return_type_tree"]["qself"]["segments"][0]["name"],
+            "T"
+        );
+        assert_eq!(
+            function["return_type_tree"]["segments"][1]["name"],
+            "Output"
+        );
+
+        // 推論される型
+        let infer = &function["body"][0]["type_tree"]["segments"][0]["arguments"][0];
+        assert_eq!(infer["value"]["type"], "Infer");
+    }
+
+    #[test]
+    fn test_json_serialization_generics_on_items() {
+        let source = r#"
+            fn foo<'a, T: Clone + 'a, const N: usize = 4>(x: &'a T) where T: Debug {}
+            struct Wrapper<T = u8> where T: Copy { value: T }
+            enum Either<L, R> { Left(L), Right(R) }
+            union Raw<T: Copy> { value: T }
+            fn plain() {}
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // 関数のライフタイム・型・const パラメータと where 句
+        let params = &items[0]["generics"]["params"];
+        assert_eq!(params[0]["kind"], "Lifetime");
+        assert_eq!(params[0]["name"], "'a");
+        assert_eq!(params[1]["kind"], "Type");
+        assert_eq!(params[1]["bounds"][0], "Clone");
+        assert_eq!(params[1]["bounds"][1], "'a");
+        assert_eq!(params[2]["kind"], "Const");
+        assert_eq!(params[2]["type_info"], "usize");
+        assert_eq!(params[2]["default"], "4");
+        let predicate = &items[0]["generics"]["where_clause"][0];
+        assert_eq!(predicate["bounded_type"], "T");
+        assert_eq!(predicate["bounds"][0], "Debug");
+
+        // 構造体・列挙型・union
+        assert_eq!(items[1]["generics"]["params"][0]["default"], "u8");
+        assert_eq!(items[1]["generics"]["where_clause"][0]["bounds"][0], "Copy");
+        assert_eq!(items[2]["generics"]["params"][1]["name"], "R");
+        assert_eq!(items[3]["generics"]["params"][0]["bounds"][0], "Copy");
+
+        // ジェネリクスがなければ出力されない
+        assert!(items[4].get("generics").is_none());
+    }
+
+    #[test]
+    fn test_json_serialization_attributes() {
+        let source = r#"
+            /// 点を表す
+            /// 構造体
+            #[derive(Debug, Clone)]
+            #[cfg(any(test, feature = "extra"))]
+            struct Point {
+                /// x 座標
+                #[serde(rename = "X")]
+                x: i32,
+            }
+            enum Mode { #[default] Fast }
+            fn f() {
+                #[allow(unused)]
+                let a = #[rustfmt::skip] 1;
+            }
+            fn plain() {}
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // ドキュメントコメントは docs にまとめられる
+        let point = &items[0];
+        assert_eq!(point["docs"], "点を表す\n構造体");
+
+        // ドキュメントコメントは書かれた位置のまま属性にも残る
+        assert_eq!(point["attributes"][0]["path"], "doc");
+        assert_eq!(point["attributes"][0]["doc"], " 点を表す");
+
+        // derive は一覧に、cfg は述語の木になる
+        assert_eq!(point["attributes"][2]["path"], "derive");
+        assert_eq!(point["attributes"][2]["derives"][1], "Clone");
+        let cfg = &point["attributes"][3]["cfg"];
+        assert_eq!(cfg["kind"], "Any");
+        assert_eq!(cfg["predicates"][0]["name"], "test");
+        assert_eq!(cfg["predicates"][1]["value"], "extra");
+
+        // フィールドとバリアントの属性
+        let field = &point["fields"][0];
+        assert_eq!(field["docs"], "x 座標");
+        assert_eq!(field["attributes"][1]["arguments"], "rename = \"X\"");
+        assert_eq!(items[1]["variants"][0]["attributes"][0]["path"], "default");
+
+        // 文と式の属性
+        let local = &items[2]["body"][0];
+        assert_eq!(local["attributes"][0]["path"], "allow");
+        assert_eq!(
+            local["initializer"]["attributes"][0]["path"],
+            "rustfmt::skip"
+        );
+
+        // 属性がなければ出力されない
+        assert!(items[3].get("attributes").is_none());
+        assert!(items[3].get("docs").is_none());
+    }
+
+    #[test]
+    fn test_json_serialization_visibility_and_qualifiers() {
+        let source = r#"
+            pub const async unsafe fn all_flags() {}
+            pub(crate) extern "C" fn callback() {}
+            extern "C" { fn printf(format: *const u8, ...) -> i32; }
+            pub(in crate::a) struct Point { pub x: i32, pub(super) y: i32, z: i32 }
+            fn private() {}
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // 関数の可視性と修飾子
+        assert_eq!(items[0]["visibility"]["kind"], "Public");
+        assert_eq!(items[0]["is_const"], true);
+        assert_eq!(items[0]["is_async"], true);
+        assert_eq!(items[0]["is_unsafe"], true);
+        assert_eq!(items[1]["visibility"]["kind"], "Crate");
+        assert_eq!(items[1]["is_extern"], true);
+        assert_eq!(items[1]["abi"], "C");
+
+        // 可変長引数
+        assert_eq!(items[2]["items"][0]["variadic"], true);
+
+        // 構造体とフィールドの可視性
+        let point = &items[3];
+        assert_eq!(point["visibility"]["kind"], "Restricted");
+        assert_eq!(point["visibility"]["path"], "crate::a");
+        assert_eq!(point["fields"][0]["visibility"]["kind"], "Public");
+        assert_eq!(point["fields"][1]["visibility"]["path"], "super");
+        assert!(point["fields"][2].get("visibility").is_none());
+
+        // 非公開で修飾子のない関数には出力されない
+        assert!(items[4].get("visibility").is_none());
+        assert!(items[4].get("is_async").is_none());
+        assert!(items[4].get("abi").is_none());
+    }
+
+    #[test]
+    fn test_json_serialization_enum_variants() {
+        let source = r#"
+            enum Msg {
+                Move { x: i32 },
+                Write(String),
+                #[allow(unused)]
+                Code = 3,
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let variants = &parsed["items"][0]["variants"];
+
+        // 名前付きフィールドのバリアント
+        assert_eq!(variants[0]["kind"], "named");
+        assert_eq!(variants[0]["fields"][0]["name"], "x");
+        assert_eq!(variants[0]["fields"][0]["type_info"], "i32");
+
+        // タプルのバリアント
+        assert_eq!(variants[1]["kind"], "tuple");
+        assert_eq!(variants[1]["fields"][0]["type_info"], "String");
+
+        // 判別式と属性を持つユニットバリアント
+        let code = &variants[2];
+        assert_eq!(code["kind"], "unit");
+        assert!(code.get("fields").is_none());
+        assert_eq!(code["discriminant"]["type"], "IntLiteral");
+        assert_eq!(code["discriminant"]["value"], "3");
+        assert_eq!(code["attributes"][0]["path"], "allow");
+    }
+
+    #[test]
+    fn test_json_serialization_struct_kinds() {
+        let source = r#"
+            struct Named { pub id: u32 }
+            struct Pair(pub u8, #[serde(skip)] u8);
+            struct Marker;
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let mut visitor = JsonVisitor::new();
+        visitor.process_file(&file);
+
+        let parsed: Value = serde_json::from_str(&visitor.to_json()).expect("JSONのパースに失敗");
+        let items = &parsed["items"];
+
+        // 名前付きフィールドの構造体にはインデックスがない
+        assert_eq!(items[0]["kind"], "named");
+        assert!(items[0]["fields"][0].get("index").is_none());
+
+        // タプル構造体は位置のインデックスを持つ
+        let pair = &items[1];
+        assert_eq!(pair["kind"], "tuple");
+        assert_eq!(pair["fields"][0]["index"], 0);
+        assert_eq!(pair["fields"][0]["visibility"]["kind"], "Public");
+        assert_eq!(pair["fields"][1]["index"], 1);
+        assert_eq!(pair["fields"][1]["attributes"][0]["path"], "serde");
+
+        // ユニット構造体
+        assert_eq!(items[2]["kind"], "unit");
+        assert_eq!(items[2]["fields"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_json_serialization_fragments() {
+        use crate::{FragmentKind, parse_rust_fragment};
+
+        // 式の断片はファイルと同じモデルで表される
+        let fragment = parse_rust_fragment("a + b * c", FragmentKind::Expr).unwrap();
+        let parsed = serde_json::to_value(&fragment).expect("JSONへの変換に失敗");
+        assert_eq!(parsed["type"], "Expression");
+        assert_eq!(parsed["expr"]["type"], "Binary");
+        assert_eq!(parsed["expr"]["right"]["operator"], "*");
+
+        // 型の断片は文字列と構造の両方を持つ
+        let fragment = parse_rust_fragment("Option<u8>", FragmentKind::Type).unwrap();
+        let parsed = serde_json::to_value(&fragment).expect("JSONへの変換に失敗");
+        assert_eq!(parsed["type"], "Type");
+        assert_eq!(parsed["type_info"], "Option<u8>");
+        assert_eq!(parsed["type_tree"]["type"], "Path");
+
+        // パターンの断片
+        let fragment = parse_rust_fragment("Some((a, _))", FragmentKind::Pat).unwrap();
+        let parsed = serde_json::to_value(&fragment).expect("JSONへの変換に失敗");
+        assert_eq!(parsed["pattern"]["type"], "TupleStruct");
+        assert_eq!(parsed["pattern"]["elements"][0]["type"], "Tuple");
+    }
+
+    #[test]
+    fn test_json_serialization_file_entries() {
+        use crate::model::{FileEntryJson, FileResultJson};
+
+        // 成功したファイルは "ast" の下にモデルを持つ
+        let file = parse_rust_source("fn f() {}").unwrap();
+        let entry = FileEntryJson {
+            path: "src/f.rs".to_string(),
+            result: FileResultJson::Ast(AstJson::from_file(&file)),
+        };
+        let parsed = serde_json::to_value(&entry).expect("JSONへの変換に失敗");
+        assert_eq!(parsed["path"], "src/f.rs");
+        assert_eq!(parsed["ast"]["items"][0]["name"], "f");
+
+        // パースエラーは "error" にメッセージとして記録される
+        let entry = FileEntryJson {
+            path: "src/bad.rs".to_string(),
+            result: FileResultJson::Error("expected `)`".to_string()),
+        };
+        let json = serde_json::to_string(&entry).expect("JSONへの変換に失敗");
+        assert_eq!(json, r#"{"path":"src/bad.rs","error":"expected `)`"}"#);
+
+        // 読み戻しても同じ値になる
+        let back: FileEntryJson = serde_json::from_str(&json).expect("JSONの読み込みに失敗");
+        assert_eq!(back, entry);
+    }
 }
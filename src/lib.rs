@@ -1,9 +1,22 @@
 //! library for parsing Rust code and visualizing AST
 //!
 //! This crate provides tools for parsing Rust source code and displaying its abstract syntax tree (AST).
+//! Source code is converted once into the typed model in [`model`], which every renderer consumes.
 
+mod attribute;
+mod builder;
+mod codegen;
+mod fragment;
 mod json_visitor;
+pub mod model;
+mod operator;
+mod pretty;
 mod text_visitor;
 
-pub use json_visitor::{AstJson, JsonVisitor};
-pub use text_visitor::{TextVisitor, parse_rust_file, parse_rust_source, print_ast};
+pub use fragment::{FragmentKind, parse_rust_fragment};
+pub use json_visitor::JsonVisitor;
+pub use model::{AstJson, FragmentJson};
+pub use text_visitor::{
+    TextVisitor, ast_to_string, fragment_to_string, parse_rust_file, parse_rust_source, print_ast,
+    write_ast,
+};
@@ -0,0 +1,1175 @@
+use serde::{Deserialize, Serialize};
+
+/// A renderer-independent representation of a Rust AST
+///
+/// The model is built once from a `syn::File` (see `AstJson::from_file`) and is
+/// consumed by every renderer, so the text and JSON outputs always carry the same
+/// information.
+///
+/// # Fields
+/// * `attributes`: Vec<AttributeJson> - the inner attributes of the file, such as `#![allow(...)]`,
+///   including its `//!` doc comments
+/// * `docs`: Option<String> - the text of the inner doc comments of the file
+/// * `items`: Vec<Node<ItemJson>> - the items in the AST
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct AstJson {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Node<ItemJson>>,
+}
+
+/// A piece of Rust source smaller than a file, such as a single expression
+///
+/// # Fields
+/// * `expr`: Node<ExprJson> - an expression such as `a + b * c`
+/// * `type_info`: String - a type as written, such as `Vec<&'a str>`
+/// * `type_tree`: Node<TypeJson> - the structure of the type
+/// * `item`: Node<ItemJson> - an item such as a function or a struct
+/// * `stmt`: Node<StmtJson> - a statement such as `let x = 1;`
+/// * `pattern`: Node<PatternJson> - a pattern such as `Some((a, _))`
+/// * `body`: Vec<Node<StmtJson>> - the statements of a block
+/// * `attributes`: Vec<AttributeJson> - attributes such as `#[derive(Debug)]`, doc comments included
+/// * `docs`: Option<String> - the text of the doc comments written among the attributes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum FragmentJson {
+    Expression {
+        expr: Node<ExprJson>,
+    },
+    Type {
+        type_info: String,
+        type_tree: Node<TypeJson>,
+    },
+    Item {
+        item: Node<ItemJson>,
+    },
+    Statement {
+        stmt: Node<StmtJson>,
+    },
+    Pattern {
+        pattern: Node<PatternJson>,
+    },
+    Block {
+        body: Vec<Node<StmtJson>>,
+    },
+    Attributes {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        attributes: Vec<AttributeJson>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        docs: Option<String>,
+    },
+}
+
+/// The outcome of parsing one file of a directory
+///
+/// # Variants
+/// * `Ast` - the model of the file, serialized as `{"ast": ...}`
+/// * `Error` - the error the file failed with, serialized as `{"error": "..."}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileResultJson {
+    Ast(AstJson),
+    Error(String),
+}
+
+/// One file of a directory, as written on one line of NDJSON output
+///
+/// # Fields
+/// * `path`: String - the path of the file
+/// * `result`: FileResultJson - the model of the file or its error, next to `path`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileEntryJson {
+    pub path: String,
+    #[serde(flatten)]
+    pub result: FileResultJson,
+}
+
+/// A model node together with its location in the source
+///
+/// The node itself is flattened when serialized, so `span` sits next to the
+/// `type` tag of the wrapped item, statement or expression.
+///
+/// # Fields
+/// * `kind`: T - the wrapped node
+/// * `attributes`: Vec<AttributeJson> - the attributes on the node in source order, doc comments included
+/// * `docs`: Option<String> - the text of the doc comments on the node, one line per `///` line;
+///   code is generated from the doc attributes when there are any, and `docs` must then match them
+/// * `span`: Option<SpanJson> - the location of the node, if known
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Node<T> {
+    #[serde(flatten)]
+    pub kind: T,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+impl<T> Node<T> {
+    /// new
+    ///
+    /// # Arguments
+    /// * `kind`: T - the wrapped node
+    ///
+    /// # Returns
+    /// * `Node<T>` - a node without location information
+    pub fn new(kind: T) -> Self {
+        Node {
+            kind,
+            attributes: Vec::new(),
+            docs: None,
+            span: None,
+        }
+    }
+}
+
+/// An outer `#[...]` or inner `#![...]` attribute
+///
+/// # Fields
+/// * `path`: String - the path of the attribute, as in `derive` or `rustfmt::skip`
+/// * `inner`: bool - whether the attribute is an inner attribute such as `#![allow(...)]`
+/// * `arguments`: Option<String> - the tokens inside the parentheses of a list attribute
+/// * `value`: Option<String> - the value of a name-value attribute, as in `#[path = "x.rs"]`
+/// * `derives`: Vec<String> - the derived traits of a `derive` attribute
+/// * `cfg`: Option<CfgJson> - the predicate of a `cfg` or `cfg_attr` attribute
+/// * `doc`: Option<String> - the text of a doc comment exactly as written, as in ` A point`
+///   for `/// A point`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttributeJson {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub inner: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derives: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<CfgJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// A configuration predicate of a `cfg` attribute
+///
+/// # Fields
+/// * `name`: String - the name of an option, as in `test` or `feature`
+/// * `value`: Option<String> - the value of a key-value option, as in `feature = "serde"`
+/// * `predicates`: Vec<CfgJson> - the operands of `all(...)` or `any(...)`
+/// * `predicate`: Box<CfgJson> - the operand of `not(...)`
+/// * `description`: String - a predicate that is not well-formed, kept as written
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum CfgJson {
+    Option {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+    All {
+        predicates: Vec<CfgJson>,
+    },
+    Any {
+        predicates: Vec<CfgJson>,
+    },
+    Not {
+        predicate: Box<CfgJson>,
+    },
+    Other {
+        description: String,
+    },
+}
+
+/// A source range
+///
+/// # Fields
+/// * `start`: PositionJson - the position of the first character
+/// * `end`: PositionJson - the position just past the last character
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanJson {
+    pub start: PositionJson,
+    pub end: PositionJson,
+}
+
+/// A position in a source file
+///
+/// # Fields
+/// * `line`: usize - the 1-based line number
+/// * `column`: usize - the 0-based column, counted in characters
+/// * `offset`: usize - the 0-based byte offset from the start of the source
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionJson {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// The visibility of an item or field that is not private
+///
+/// # Fields
+/// * `path`: String - the module an item is visible in, as in `super` or `crate::a`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum VisibilityJson {
+    /// `pub`
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(self)`, `pub(super)` or `pub(in path)`
+    Restricted { path: String },
+}
+
+/// # Fields
+/// * `name`: String - the name of the item
+/// * `visibility`: Option<VisibilityJson> - the visibility of the item, None when it is private
+/// * `is_const`: bool - whether a function is a `const fn`
+/// * `is_async`: bool - whether a function is an `async fn`
+/// * `is_extern`: bool - whether a function is declared with `extern`
+/// * `variadic`: bool - whether a function takes C-style variadic arguments, as in `printf(fmt: *const c_char, ...)`
+/// * `parameters`: Vec<ParameterJson> - the parameters of the item
+/// * `return_type`: Option<String> - the return type of the item
+/// * `return_type_tree`: Option<Node<TypeJson>> - the structure of the return type
+/// * `body`: Option<Vec<Node<StmtJson>>> - the body of the item, None for a declaration without one
+/// * `kind`: FieldsKind - whether a struct has named, tuple or no fields
+/// * `fields`: Vec<FieldJson> - the fields of a struct or union
+/// * `variants`: Vec<VariantJson> - the variants of an enum
+/// * `generics`: GenericsJson - the generic parameters and where clause of the item
+/// * `is_unsafe`: bool - whether a function, impl, trait, module or extern block is `unsafe`
+/// * `is_default`: bool - whether an impl is a specializable `default impl`
+/// * `negative`: bool - whether an impl is a negative impl such as `impl !Send for T`
+/// * `self_type`: String - the type an impl block is for
+/// * `self_type_tree`: Node<TypeJson> - the structure of the type an impl block is for
+/// * `trait_name`: Option<String> - the trait an impl block implements
+/// * `is_auto`: bool - whether a trait is an `auto trait`
+/// * `supertraits`: Vec<String> - the bounds of a trait, such as `Clone` in `trait T: Clone`
+/// * `items`: Vec<Node<ItemJson>> - the items nested in an impl, trait, module or extern block
+/// * `inline`: bool - whether a module is written inline, as opposed to a `mod name;` declaration
+/// * `leading_colon`: bool - whether a use tree starts with `::`
+/// * `tree`: String - the imported use tree
+/// * `paths`: Vec<UsePathJson> - the use tree flattened into one entry per imported path
+/// * `alias`: Option<String> - the `as` name of an extern crate
+/// * `bounds`: Vec<String> - the bounds of an associated type, such as `Clone` in `type A: Clone;`
+/// * `mutable`: bool - whether a static is `static mut`
+/// * `type_info`: String - the type of a const, static or type alias
+/// * `type_tree`: Node<TypeJson> - the structure of the type of a const, static or type alias
+/// * `value`: Option<Box<Node<ExprJson>>> - the value of a const or static
+/// * `path`: String - the path of a macro, such as `macro_rules`
+/// * `delimiter`: MacroDelimiter - the brackets around the tokens passed to a macro
+/// * `tokens`: String - the tokens passed to a macro
+/// * `rules`: Vec<MacroRuleJson> - the rule arms of a `macro_rules!` definition
+/// * `abi`: Option<String> - the ABI of an extern block or function, as in `"C"` for `extern "C"`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ItemJson {
+    Function {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_const: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_async: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_extern: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        abi: Option<String>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        parameters: Vec<ParameterJson>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        variadic: bool,
+        return_type: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        return_type_tree: Option<Node<TypeJson>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body: Option<Vec<Node<StmtJson>>>,
+    },
+    Struct {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        kind: FieldsKind,
+        fields: Vec<FieldJson>,
+    },
+    Enum {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        variants: Vec<VariantJson>,
+    },
+    Union {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        fields: Vec<FieldJson>,
+    },
+    Impl {
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_default: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        negative: bool,
+        self_type: String,
+        self_type_tree: Node<TypeJson>,
+        trait_name: Option<String>,
+        items: Vec<Node<ItemJson>>,
+    },
+    Trait {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_auto: bool,
+        supertraits: Vec<String>,
+        items: Vec<Node<ItemJson>>,
+    },
+    Module {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        inline: bool,
+        items: Option<Vec<Node<ItemJson>>>,
+    },
+    Use {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        leading_colon: bool,
+        tree: String,
+        paths: Vec<UsePathJson>,
+    },
+    ExternCrate {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        alias: Option<String>,
+    },
+    Const {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        type_info: String,
+        type_tree: Node<TypeJson>,
+        value: Option<Box<Node<ExprJson>>>,
+    },
+    Static {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        mutable: bool,
+        type_info: String,
+        type_tree: Node<TypeJson>,
+        value: Option<Box<Node<ExprJson>>>,
+    },
+    TypeAlias {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<VisibilityJson>,
+        #[serde(default, skip_serializing_if = "GenericsJson::is_empty")]
+        generics: GenericsJson,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        bounds: Vec<String>,
+        type_info: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        type_tree: Option<Node<TypeJson>>,
+    },
+    Macro {
+        name: Option<String>,
+        path: String,
+        delimiter: MacroDelimiter,
+        tokens: String,
+    },
+    MacroRules {
+        name: String,
+        rules: Vec<MacroRuleJson>,
+    },
+    ExternBlock {
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        abi: Option<String>,
+        items: Vec<Node<ItemJson>>,
+    },
+    Other {
+        description: String,
+    },
+}
+
+/// The delimiter a macro invocation is written with
+///
+/// # Variants
+/// * `Paren` - `name!(...)`
+/// * `Bracket` - `name![...]`
+/// * `Brace` - `name! { ... }`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroDelimiter {
+    Paren,
+    Bracket,
+    Brace,
+}
+
+/// One arm of a `macro_rules!` definition
+///
+/// # Fields
+/// * `matcher`: String - the pattern of the arm, including its delimiters
+/// * `transcriber`: String - the expansion of the arm, including its delimiters
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MacroRuleJson {
+    pub matcher: String,
+    pub transcriber: String,
+}
+
+/// One path imported by a use tree
+///
+/// `use std::{fmt::{self, Display as D}, io::*};` flattens into `std::fmt`,
+/// `std::fmt::Display` (alias `D`) and `std::io` (glob).
+///
+/// # Fields
+/// * `path`: String - the full path, joined with `::`
+/// * `alias`: Option<String> - the `as` name, `_` for an underscore import
+/// * `glob`: bool - whether every item under `path` is imported with `*`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UsePathJson {
+    pub path: String,
+    pub alias: Option<String>,
+    pub glob: bool,
+}
+
+/// # Fields
+/// * `params`: Vec<GenericParamJson> - the generic parameters, in declaration order
+/// * `where_clause`: Vec<WherePredicateJson> - the predicates of the where clause
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct GenericsJson {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<GenericParamJson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub where_clause: Vec<WherePredicateJson>,
+}
+
+impl GenericsJson {
+    /// is_empty
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the GenericsJson
+    ///
+    /// # Returns
+    /// * `bool` - true when there are neither parameters nor where predicates
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty() && self.where_clause.is_empty()
+    }
+}
+
+/// # Fields
+/// * `name`: String - the name of the parameter, including the `'` of a lifetime
+/// * `bounds`: Vec<String> - the bounds of a type or lifetime parameter
+/// * `default`: Option<String> - the default of a type or const parameter
+/// * `type_info`: String - the type of a const parameter
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum GenericParamJson {
+    Type {
+        name: String,
+        bounds: Vec<String>,
+        default: Option<String>,
+    },
+    Lifetime {
+        name: String,
+        bounds: Vec<String>,
+    },
+    Const {
+        name: String,
+        type_info: String,
+        default: Option<String>,
+    },
+}
+
+/// # Fields
+/// * `lifetimes`: Vec<String> - the higher-ranked lifetimes of a `for<'a>` predicate
+/// * `bounded_type`: String - the type being bounded
+/// * `lifetime`: String - the lifetime being bounded
+/// * `bounds`: Vec<String> - the bounds of the predicate
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum WherePredicateJson {
+    Type {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        lifetimes: Vec<String>,
+        bounded_type: String,
+        bounds: Vec<String>,
+    },
+    Lifetime {
+        lifetime: String,
+        bounds: Vec<String>,
+    },
+}
+
+/// # Fields
+/// * `name`: String - the name of the parameter, `self` for a receiver, or the pattern text when it destructures
+/// * `pattern`: Option<Node<PatternJson>> - the pattern binding the argument, absent for a receiver
+/// * `type_info`: String - the type of the parameter
+/// * `type_tree`: Node<TypeJson> - the structure of the type of the parameter
+/// * `attributes`: Vec<AttributeJson> - the attributes on the parameter, such as `#[cfg(...)]`
+/// * `span`: Option<SpanJson> - the location of the parameter
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParameterJson {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Node<PatternJson>>,
+    pub type_info: String,
+    pub type_tree: Node<TypeJson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// # Fields
+/// * `name`: Option<String> - the name of the field, None for a tuple field
+/// * `index`: Option<usize> - the position of a tuple field, as in `.0`
+/// * `visibility`: Option<VisibilityJson> - the visibility of the field, None when it is private
+/// * `type_info`: String - the type of the field
+/// * `type_tree`: Node<TypeJson> - the structure of the type of the field
+/// * `attributes`: Vec<AttributeJson> - the attributes on the field, doc comments included
+/// * `docs`: Option<String> - the text of the doc comments on the field
+/// * `span`: Option<SpanJson> - the location of the field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldJson {
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<VisibilityJson>,
+    pub type_info: String,
+    pub type_tree: Node<TypeJson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// # Fields
+/// * `name`: String - the name of the variant
+/// * `kind`: FieldsKind - whether the variant has named, tuple or no fields
+/// * `fields`: Vec<FieldJson> - the fields of the variant
+/// * `discriminant`: Option<Node<ExprJson>> - the explicit discriminant, as in `Code = 3`
+/// * `attributes`: Vec<AttributeJson> - the attributes on the variant, doc comments included
+/// * `docs`: Option<String> - the text of the doc comments on the variant
+/// * `span`: Option<SpanJson> - the location of the variant
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VariantJson {
+    pub name: String,
+    pub kind: FieldsKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discriminant: Option<Node<ExprJson>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// # Variants
+/// * `Named` - fields with names, as in `{ x: i32 }`
+/// * `Tuple` - positional fields, as in `(i32, String)`
+/// * `Unit` - no fields at all
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldsKind {
+    Named,
+    Tuple,
+    Unit,
+}
+
+/// # Fields
+/// * `name`: Option<String> - the bound name when the pattern is a plain identifier
+/// * `pattern`: Box<Node<PatternJson>> - the pattern of a `let`
+/// * `type_info`: Option<String> - the declared type of a `let`
+/// * `type_tree`: Option<Box<Node<TypeJson>>> - the structure of the declared type of a `let`
+/// * `initializer`: Option<Box<Node<ExprJson>>> - the initializer of the statement
+/// * `else_branch`: Option<Box<Node<ExprJson>>> - the diverging block of a `let ... else`
+/// * `semicolon`: bool - whether an expression statement ends with `;`
+/// * `item`: Box<Node<ItemJson>> - an item declared inside a block
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum StmtJson {
+    VariableDeclaration {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        pattern: Box<Node<PatternJson>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        type_info: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        type_tree: Option<Box<Node<TypeJson>>>,
+        initializer: Option<Box<Node<ExprJson>>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        else_branch: Option<Box<Node<ExprJson>>>,
+    },
+    Expression {
+        expr: Box<Node<ExprJson>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        semicolon: bool,
+    },
+    Item {
+        item: Box<Node<ItemJson>>,
+    },
+    Other {
+        description: String,
+    },
+}
+
+/// # Fields
+/// * `value`: String - the value of the literal, with escapes resolved
+/// * `suffix`: Option<String> - the type suffix of a numeric literal, as in `10u8`
+/// * `raw`: String - the literal exactly as written in the source
+/// * `path`: String - the path of the struct being constructed
+/// * `fields`: Vec<FieldValueJson> - the fields given in a struct literal
+/// * `base`: Option<Box<Node<ExprJson>>> - the `..base` a struct literal copies the remaining fields from
+/// * `elements`: Vec<Node<ExprJson>> - the elements of a tuple or array
+/// * `length`: Box<Node<ExprJson>> - the length of an array repeat expression `[value; length]`
+/// * `start`: Option<Box<Node<ExprJson>>> - the lower bound of a range
+/// * `end`: Option<Box<Node<ExprJson>>> - the upper bound of a range
+/// * `inclusive`: bool - whether a range is written with `..=`
+/// * `operator`: String - the operator of a binary, unary or compound assignment expression
+/// * `category`: OperatorCategory - the kind of operation the operator performs
+/// * `expr`: Box<Node<ExprJson>> - the operand of a match, reference, cast, `let`, `?`, `.await` or parentheses
+/// * `arms`: Vec<MatchArmJson> - the arms of a match
+/// * `lifetimes`: Vec<String> - the higher-ranked lifetimes of a `for<'a> |x: &'a T|` closure
+/// * `is_const`: bool - whether a closure is a `const` closure
+/// * `is_static`: bool - whether a closure is a `static` closure, which makes an immovable coroutine
+/// * `is_move`: bool - whether a closure or async block is `move`
+/// * `is_async`: bool - whether a closure is an `async` closure
+/// * `parameters`: Vec<ClosureParamJson> - the parameters of a closure
+/// * `return_type`: Option<String> - the declared return type of a closure
+/// * `return_type_tree`: Option<Node<TypeJson>> - the structure of the declared return type of a closure
+/// * `receiver`: Box<Node<ExprJson>> - the receiver of a method call
+/// * `method`: String - the name of the called method
+/// * `turbofish`: Vec<String> - the generic arguments of a method call, as in `collect::<Vec<_>>()`
+/// * `base`: Box<Node<ExprJson>> - the expression a field or index is taken from
+/// * `member`: String - the accessed field, a name or a tuple index
+/// * `index`: Box<Node<ExprJson>> - the index expression
+/// * `operand`: Box<Node<ExprJson>> - the operand of a unary expression
+/// * `mutable`: bool - whether a reference is `&mut`
+/// * `type_info`: String - the target type of a cast
+/// * `type_tree`: Node<TypeJson> - the structure of the target type of a cast
+/// * `target`: Box<Node<ExprJson>> - the place being assigned to
+/// * `label`: Option<String> - the label of a loop or block, or the label a `break`/`continue` targets
+/// * `pattern`: Box<Node<PatternJson>> - the pattern bound by a `for` loop or a `let` condition
+/// * `iterable`: Box<Node<ExprJson>> - the expression a `for` loop iterates over
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ExprJson {
+    IntLiteral {
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        suffix: Option<String>,
+        raw: String,
+    },
+    FloatLiteral {
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        suffix: Option<String>,
+        raw: String,
+    },
+    StringLiteral {
+        value: String,
+        raw: String,
+    },
+    BoolLiteral {
+        value: bool,
+    },
+    CharLiteral {
+        value: char,
+        raw: String,
+    },
+    ByteLiteral {
+        value: u8,
+        raw: String,
+    },
+    ByteStringLiteral {
+        value: Vec<u8>,
+        raw: String,
+    },
+    CStringLiteral {
+        value: String,
+        raw: String,
+    },
+    VerbatimLiteral {
+        raw: String,
+    },
+    StructLiteral {
+        path: String,
+        fields: Vec<FieldValueJson>,
+        base: Option<Box<Node<ExprJson>>>,
+    },
+    Tuple {
+        elements: Vec<Node<ExprJson>>,
+    },
+    Array {
+        elements: Vec<Node<ExprJson>>,
+    },
+    ArrayRepeat {
+        value: Box<Node<ExprJson>>,
+        length: Box<Node<ExprJson>>,
+    },
+    Range {
+        start: Option<Box<Node<ExprJson>>>,
+        end: Option<Box<Node<ExprJson>>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        inclusive: bool,
+    },
+    Parenthesized {
+        expr: Box<Node<ExprJson>>,
+    },
+    Binary {
+        operator: String,
+        category: OperatorCategory,
+        left: Box<Node<ExprJson>>,
+        right: Box<Node<ExprJson>>,
+    },
+    FunctionCall {
+        function: Box<Node<ExprJson>>,
+        arguments: Vec<Node<ExprJson>>,
+    },
+    Identifier {
+        name: String,
+    },
+    If {
+        condition: Box<Node<ExprJson>>,
+        then_branch: Vec<Node<StmtJson>>,
+        else_branch: Option<Box<Node<ExprJson>>>,
+    },
+    Loop {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        body: Vec<Node<StmtJson>>,
+    },
+    While {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        condition: Box<Node<ExprJson>>,
+        body: Vec<Node<StmtJson>>,
+    },
+    ForLoop {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        pattern: Box<Node<PatternJson>>,
+        iterable: Box<Node<ExprJson>>,
+        body: Vec<Node<StmtJson>>,
+    },
+    Break {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        value: Option<Box<Node<ExprJson>>>,
+    },
+    Continue {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+    Return {
+        value: Option<Box<Node<ExprJson>>>,
+    },
+    Let {
+        pattern: Box<Node<PatternJson>>,
+        expr: Box<Node<ExprJson>>,
+    },
+    Try {
+        expr: Box<Node<ExprJson>>,
+    },
+    Await {
+        expr: Box<Node<ExprJson>>,
+    },
+    Match {
+        expr: Box<Node<ExprJson>>,
+        arms: Vec<MatchArmJson>,
+    },
+    Closure {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        lifetimes: Vec<String>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_const: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_static: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_move: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_async: bool,
+        parameters: Vec<ClosureParamJson>,
+        return_type: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        return_type_tree: Option<Node<TypeJson>>,
+        body: Box<Node<ExprJson>>,
+    },
+    MethodCall {
+        receiver: Box<Node<ExprJson>>,
+        method: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        turbofish: Vec<String>,
+        arguments: Vec<Node<ExprJson>>,
+    },
+    FieldAccess {
+        base: Box<Node<ExprJson>>,
+        member: String,
+    },
+    Index {
+        base: Box<Node<ExprJson>>,
+        index: Box<Node<ExprJson>>,
+    },
+    Unary {
+        operator: String,
+        category: OperatorCategory,
+        operand: Box<Node<ExprJson>>,
+    },
+    Reference {
+        mutable: bool,
+        expr: Box<Node<ExprJson>>,
+    },
+    Cast {
+        expr: Box<Node<ExprJson>>,
+        type_info: String,
+        type_tree: Node<TypeJson>,
+    },
+    Assign {
+        target: Box<Node<ExprJson>>,
+        value: Box<Node<ExprJson>>,
+    },
+    CompoundAssign {
+        operator: String,
+        category: OperatorCategory,
+        target: Box<Node<ExprJson>>,
+        value: Box<Node<ExprJson>>,
+    },
+    Block {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        body: Vec<Node<StmtJson>>,
+    },
+    AsyncBlock {
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_move: bool,
+        body: Vec<Node<StmtJson>>,
+    },
+    UnsafeBlock {
+        body: Vec<Node<StmtJson>>,
+    },
+    ConstBlock {
+        body: Vec<Node<StmtJson>>,
+    },
+    Other {
+        description: String,
+    },
+}
+
+/// # Fields
+/// * `pattern`: Node<PatternJson> - the pattern of the arm
+/// * `guard`: Option<Box<Node<ExprJson>>> - the `if` guard of the arm
+/// * `body`: Box<Node<ExprJson>> - the expression evaluated when the arm matches
+/// * `attributes`: Vec<AttributeJson> - the attributes on the arm, such as `#[cfg(...)]`
+/// * `docs`: Option<String> - the text of the doc comments on the arm
+/// * `span`: Option<SpanJson> - the location of the arm
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MatchArmJson {
+    pub pattern: Node<PatternJson>,
+    pub guard: Option<Box<Node<ExprJson>>>,
+    pub body: Box<Node<ExprJson>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// # Fields
+/// * `pattern`: Node<PatternJson> - the pattern binding the argument
+/// * `type_info`: Option<String> - the declared type of the parameter
+/// * `type_tree`: Option<Node<TypeJson>> - the structure of the declared type of the parameter
+/// * `span`: Option<SpanJson> - the location of the parameter
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClosureParamJson {
+    pub pattern: Node<PatternJson>,
+    pub type_info: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_tree: Option<Node<TypeJson>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// # Fields
+/// * `name`: String - the name bound by an identifier pattern
+/// * `by_ref`: bool - whether an identifier binds by `ref`
+/// * `mutable`: bool - whether an identifier binds with `mut`, or a reference pattern is `&mut`
+/// * `subpattern`: Option<Box<Node<PatternJson>>> - the pattern after `@` in a binding like `n @ 1..=9`
+/// * `value`: Box<Node<ExprJson>> - the literal a literal pattern matches
+/// * `start`: Option<Box<Node<ExprJson>>> - the lower bound of a range pattern
+/// * `end`: Option<Box<Node<ExprJson>>> - the upper bound of a range pattern
+/// * `inclusive`: bool - whether a range pattern is written with `..=`
+/// * `path`: String - the path of a constant, unit variant, struct or tuple struct
+/// * `elements`: Vec<Node<PatternJson>> - the sub-patterns of a tuple, tuple struct or slice
+/// * `fields`: Vec<FieldPatternJson> - the fields matched by a struct pattern
+/// * `has_rest`: bool - whether a struct pattern ends with `..`
+/// * `pattern`: Box<Node<PatternJson>> - the pattern behind a reference pattern
+/// * `cases`: Vec<Node<PatternJson>> - the alternatives of an or-pattern
+/// * `description`: String - the source text of a pattern without a dedicated variant
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum PatternJson {
+    Identifier {
+        name: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        by_ref: bool,
+        #[serde(default, skip_serializing_if = "is_false")]
+        mutable: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subpattern: Option<Box<Node<PatternJson>>>,
+    },
+    Wildcard,
+    Rest,
+    Literal {
+        value: Box<Node<ExprJson>>,
+    },
+    Range {
+        start: Option<Box<Node<ExprJson>>>,
+        end: Option<Box<Node<ExprJson>>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        inclusive: bool,
+    },
+    Path {
+        path: String,
+    },
+    Tuple {
+        elements: Vec<Node<PatternJson>>,
+    },
+    TupleStruct {
+        path: String,
+        elements: Vec<Node<PatternJson>>,
+    },
+    Struct {
+        path: String,
+        fields: Vec<FieldPatternJson>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        has_rest: bool,
+    },
+    Slice {
+        elements: Vec<Node<PatternJson>>,
+    },
+    Reference {
+        #[serde(default, skip_serializing_if = "is_false")]
+        mutable: bool,
+        pattern: Box<Node<PatternJson>>,
+    },
+    Or {
+        cases: Vec<Node<PatternJson>>,
+    },
+    Other {
+        description: String,
+    },
+}
+
+/// # Fields
+/// * `member`: String - the name or tuple index of the field
+/// * `pattern`: Node<PatternJson> - the pattern the field is matched against
+/// * `shorthand`: bool - whether the field is written as `Point { x }` instead of `Point { x: x }`
+/// * `attributes`: Vec<AttributeJson> - the attributes on the field, such as `#[cfg(...)]`
+/// * `span`: Option<SpanJson> - the location of the field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldPatternJson {
+    pub member: String,
+    pub pattern: Node<PatternJson>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub shorthand: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// The structure of a type
+///
+/// It sits next to the type as written, such as a `type_tree` next to a `type_info`. Code is
+/// generated from the type as written, and the structure must describe the same type.
+///
+/// # Fields
+/// * `qself`: Option<Box<Node<TypeJson>>> - the self type of a qualified path such as `<T as Trait>::Output`
+/// * `leading_colon`: bool - whether a path starts with `::`
+/// * `segments`: Vec<PathSegmentJson> - the segments of a path
+/// * `lifetime`: Option<String> - the lifetime of a reference
+/// * `mutable`: bool - whether a reference or raw pointer is mutable
+/// * `elem`: Box<Node<TypeJson>> - the pointee of a reference or pointer, or the element of a slice or array
+/// * `length`: Box<Node<ExprJson>> - the length of an array
+/// * `elements`: Vec<Node<TypeJson>> - the elements of a tuple, empty for `()`
+/// * `lifetimes`: Vec<String> - the higher-ranked lifetimes of a `for<'a> fn` pointer
+/// * `is_unsafe`: bool - whether a function pointer is `unsafe`
+/// * `abi`: Option<String> - the ABI of a function pointer, such as `C`
+/// * `inputs`: Vec<Node<TypeJson>> - the parameter types of a function pointer
+/// * `variadic`: bool - whether a function pointer ends with `...`
+/// * `output`: Option<Box<Node<TypeJson>>> - the return type of a function pointer
+/// * `bounds`: Vec<String> - the bounds of an `impl Trait` or `dyn Trait` type
+/// * `description`: String - the source text of a type without a dedicated variant, such as a macro
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum TypeJson {
+    Path {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        qself: Option<Box<Node<TypeJson>>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        leading_colon: bool,
+        segments: Vec<PathSegmentJson>,
+    },
+    Reference {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lifetime: Option<String>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        mutable: bool,
+        elem: Box<Node<TypeJson>>,
+    },
+    Pointer {
+        mutable: bool,
+        elem: Box<Node<TypeJson>>,
+    },
+    Slice {
+        elem: Box<Node<TypeJson>>,
+    },
+    Array {
+        elem: Box<Node<TypeJson>>,
+        length: Box<Node<ExprJson>>,
+    },
+    Tuple {
+        elements: Vec<Node<TypeJson>>,
+    },
+    FnPointer {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        lifetimes: Vec<String>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        is_unsafe: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        abi: Option<String>,
+        inputs: Vec<Node<TypeJson>>,
+        #[serde(default, skip_serializing_if = "is_false")]
+        variadic: bool,
+        output: Option<Box<Node<TypeJson>>>,
+    },
+    ImplTrait {
+        bounds: Vec<String>,
+    },
+    TraitObject {
+        bounds: Vec<String>,
+    },
+    Never,
+    Infer,
+    Other {
+        description: String,
+    },
+}
+
+/// # Fields
+/// * `name`: String - the identifier of the segment
+/// * `arguments`: Vec<GenericArgumentJson> - the angle-bracketed arguments, as in `Vec<T>`
+/// * `parenthesized`: bool - whether the segment takes `Fn(A) -> B` style arguments
+/// * `inputs`: Vec<Node<TypeJson>> - the parenthesized argument types
+/// * `output`: Option<Box<Node<TypeJson>>> - the return type of a parenthesized segment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PathSegmentJson {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<GenericArgumentJson>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub parenthesized: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<Node<TypeJson>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Box<Node<TypeJson>>>,
+}
+
+/// # Fields
+/// * `name`: String - the lifetime, or the associated item an argument binds
+/// * `value`: Node<TypeJson> - the type argument or the bound associated type
+/// * `expr`: String - a const argument
+/// * `bounds`: Vec<String> - the bounds of an associated type constraint, as in `Item: Clone`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum GenericArgumentJson {
+    Lifetime { name: String },
+    Type { value: Node<TypeJson> },
+    Const { expr: String },
+    AssocType { name: String, value: Node<TypeJson> },
+    AssocConst { name: String, expr: String },
+    Constraint { name: String, bounds: Vec<String> },
+}
+
+/// # Variants
+/// * `Arithmetic` - `+`, `-`, `*`, `/`, `%` and unary `-`
+/// * `Logical` - `&&`, `||` and `!`
+/// * `Comparison` - `==`, `!=`, `<`, `<=`, `>`, `>=`
+/// * `Bitwise` - `&`, `|`, `^`, `<<`, `>>`
+/// * `Assignment` - compound assignments such as `+=` and `<<=`
+/// * `Dereference` - unary `*`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorCategory {
+    Arithmetic,
+    Logical,
+    Comparison,
+    Bitwise,
+    Assignment,
+    Dereference,
+}
+
+/// # Fields
+/// * `member`: String - the name or tuple index of the field
+/// * `value`: Node<ExprJson> - the value assigned to the field
+/// * `shorthand`: bool - whether the field is written as `Point { x }` instead of `Point { x: x }`
+/// * `attributes`: Vec<AttributeJson> - the attributes on the field, such as `#[cfg(...)]`
+/// * `docs`: Option<String> - the text of the doc comments on the field
+/// * `span`: Option<SpanJson> - the location of the field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldValueJson {
+    pub member: String,
+    pub value: Node<ExprJson>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub shorthand: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanJson>,
+}
+
+/// is_false
+///
+/// # Arguments
+/// * `value`: &bool - a flag of a model node
+///
+/// # Returns
+/// * `bool` - true when the flag is unset and can be left out of the JSON
+fn is_false(value: &bool) -> bool {
+    !*value
+}
@@ -0,0 +1,106 @@
+use syn::{BinOp, UnOp};
+
+use crate::model::OperatorCategory;
+
+/// binary_operator
+///
+/// # Arguments
+/// * `op`: &BinOp - a binary or compound assignment operator
+///
+/// # Returns
+/// * `Option<(&'static str, OperatorCategory)>` - the operator as written and its category,
+///   or None for an operator this table does not know yet
+pub(crate) fn binary_operator(op: &BinOp) -> Option<(&'static str, OperatorCategory)> {
+    use OperatorCategory::*;
+
+    let operator = match op {
+        BinOp::Add(_) => ("+", Arithmetic),
+        BinOp::Sub(_) => ("-", Arithmetic),
+        BinOp::Mul(_) => ("*", Arithmetic),
+        BinOp::Div(_) => ("/", Arithmetic),
+        BinOp::Rem(_) => ("%", Arithmetic),
+        BinOp::And(_) => ("&&", Logical),
+        BinOp::Or(_) => ("||", Logical),
+        BinOp::BitXor(_) => ("^", Bitwise),
+        BinOp::BitAnd(_) => ("&", Bitwise),
+        BinOp::BitOr(_) => ("|", Bitwise),
+        BinOp::Shl(_) => ("<<", Bitwise),
+        BinOp::Shr(_) => (">>", Bitwise),
+        BinOp::Eq(_) => ("==", Comparison),
+        BinOp::Lt(_) => ("<", Comparison),
+        BinOp::Le(_) => ("<=", Comparison),
+        BinOp::Ne(_) => ("!=", Comparison),
+        BinOp::Ge(_) => (">=", Comparison),
+        BinOp::Gt(_) => (">", Comparison),
+        BinOp::AddAssign(_) => ("+=", Assignment),
+        BinOp::SubAssign(_) => ("-=", Assignment),
+        BinOp::MulAssign(_) => ("*=", Assignment),
+        BinOp::DivAssign(_) => ("/=", Assignment),
+        BinOp::RemAssign(_) => ("%=", Assignment),
+        BinOp::BitXorAssign(_) => ("^=", Assignment),
+        BinOp::BitAndAssign(_) => ("&=", Assignment),
+        BinOp::BitOrAssign(_) => ("|=", Assignment),
+        BinOp::ShlAssign(_) => ("<<=", Assignment),
+        BinOp::ShrAssign(_) => (">>=", Assignment),
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+/// unary_operator
+///
+/// # Arguments
+/// * `op`: &UnOp - a unary operator
+///
+/// # Returns
+/// * `Option<(&'static str, OperatorCategory)>` - the operator as written and its category,
+///   or None for an operator this table does not know yet
+pub(crate) fn unary_operator(op: &UnOp) -> Option<(&'static str, OperatorCategory)> {
+    let operator = match op {
+        UnOp::Deref(_) => ("*", OperatorCategory::Dereference),
+        UnOp::Not(_) => ("!", OperatorCategory::Logical),
+        UnOp::Neg(_) => ("-", OperatorCategory::Arithmetic),
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_binary_operator_table() {
+        let cases: Vec<(BinOp, &str, OperatorCategory)> = vec![
+            (parse_quote!(%), "%", OperatorCategory::Arithmetic),
+            (parse_quote!(||), "||", OperatorCategory::Logical),
+            (parse_quote!(>>), ">>", OperatorCategory::Bitwise),
+            (parse_quote!(!=), "!=", OperatorCategory::Comparison),
+            (parse_quote!(<<=), "<<=", OperatorCategory::Assignment),
+        ];
+
+        for (op, symbol, category) in cases {
+            assert_eq!(binary_operator(&op), Some((symbol, category)));
+        }
+    }
+
+    #[test]
+    fn test_unary_operator_table() {
+        let deref: UnOp = parse_quote!(*);
+        let not: UnOp = parse_quote!(!);
+        let neg: UnOp = parse_quote!(-);
+
+        assert_eq!(
+            unary_operator(&deref),
+            Some(("*", OperatorCategory::Dereference))
+        );
+        assert_eq!(unary_operator(&not), Some(("!", OperatorCategory::Logical)));
+        assert_eq!(
+            unary_operator(&neg),
+            Some(("-", OperatorCategory::Arithmetic))
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+
+/// A token flattened out of a token stream, with multi-character operators joined back together
+#[derive(Debug, Clone, PartialEq)]
+enum Atom {
+    Word(String),
+    Lifetime(String),
+    Op(String),
+    Open(char),
+    Close(char),
+    GroupOpen,
+    GroupClose,
+}
+
+/// operators that are printed as one unit when their characters are joint, longest first
+///
+/// `<<` and `>>` are left out, because `>>` also closes two generic argument lists
+const JOINED_OPS: [&str; 22] = [
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=",
+    "*=", "/=", "%=", "^=", "&=", "|=", "..",
+];
+
+/// keywords that keep a space before a following `(` or `[`
+const SPACED_KEYWORDS: [&str; 7] = ["mut", "const", "dyn", "impl", "as", "unsafe", "in"];
+
+/// operators that are unary when they do not follow an operand
+const PREFIX_OPS: [&str; 6] = ["&", "&&", "*", "!", "?", "-"];
+
+/// keywords that can never end an operand, so an operator after them is unary
+const NON_OPERAND_KEYWORDS: [&str; 35] = [
+    "as", "async", "break", "const", "continue", "dyn", "else", "enum", "extern", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "static", "struct", "trait", "type", "unsafe", "use", "where", "while", "yield", "box",
+    "become", "do",
+];
+
+/// pretty_tokens
+///
+/// # Arguments
+/// * `tokens`: &T - the syntax to print, usually a type, bound or path
+///
+/// # Returns
+/// * `String` - the tokens with conventional Rust spacing, as in `Vec<&'a str>` instead of `Vec < & 'a str >`
+pub(crate) fn pretty_tokens<T: ToTokens>(tokens: &T) -> String {
+    let mut atoms = Vec::new();
+    flatten(tokens.to_token_stream(), &mut atoms);
+
+    let mut text = String::new();
+    for (i, atom) in atoms.iter().enumerate() {
+        if i > 0 {
+            let before = i.checked_sub(2).map(|j| &atoms[j]);
+            if needs_space(before, &atoms[i - 1], atom) {
+                text.push(' ');
+            }
+        }
+        match atom {
+            Atom::Word(word) | Atom::Lifetime(word) | Atom::Op(word) => text.push_str(word),
+            Atom::Open(c) | Atom::Close(c) => text.push(*c),
+            Atom::GroupOpen => text.push('{'),
+            Atom::GroupClose => text.push('}'),
+        }
+    }
+    text
+}
+
+/// flatten
+///
+/// # Arguments
+/// * `stream`: TokenStream - the tokens to flatten
+/// * `atoms`: &mut Vec<Atom> - the list the atoms are appended to
+///
+/// # Returns
+/// * `()`
+fn flatten(stream: TokenStream, atoms: &mut Vec<Atom>) {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Ident(ident) => atoms.push(Atom::Word(ident.to_string())),
+            TokenTree::Literal(literal) => atoms.push(Atom::Word(literal.to_string())),
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace
+                    && atoms.last() == Some(&Atom::Op("::".to_string())) =>
+            {
+                atoms.push(Atom::GroupOpen);
+                flatten(group.stream(), atoms);
+                atoms.push(Atom::GroupClose);
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => (Some('('), Some(')')),
+                    Delimiter::Bracket => (Some('['), Some(']')),
+                    Delimiter::Brace => (Some('{'), Some('}')),
+                    Delimiter::None => (None, None),
+                };
+                atoms.extend(open.map(Atom::Open));
+                flatten(group.stream(), atoms);
+                atoms.extend(close.map(Atom::Close));
+            }
+            TokenTree::Punct(punct) => {
+                if punct.as_char() == '\''
+                    && let Some(TokenTree::Ident(ident)) = tokens.get(i + 1)
+                {
+                    atoms.push(Atom::Lifetime(format!("'{}", ident)));
+                    i += 2;
+                    continue;
+                }
+
+                let joined = JOINED_OPS.iter().find(|op| joint_op_at(&tokens, i, op));
+                match joined {
+                    Some(op) => {
+                        atoms.push(Atom::Op(op.to_string()));
+                        i += op.len();
+                        continue;
+                    }
+                    None => atoms.push(Atom::Op(punct.as_char().to_string())),
+                }
+            }
+        }
+        i += 1;
+    }
+}
+
+/// joint_op_at
+///
+/// # Arguments
+/// * `tokens`: &[TokenTree] - the tokens of one group
+/// * `start`: usize - the index of the first punctuation character
+/// * `op`: &str - the operator to look for
+///
+/// # Returns
+/// * `bool` - whether the operator is spelled out by joint punctuation starting at `start`
+fn joint_op_at(tokens: &[TokenTree], start: usize, op: &str) -> bool {
+    let last = op.len() - 1;
+    op.chars().enumerate().all(|(offset, c)| {
+        matches!(
+            tokens.get(start + offset),
+            Some(TokenTree::Punct(punct))
+                if punct.as_char() == c && (offset == last || punct.spacing() == Spacing::Joint)
+        )
+    })
+}
+
+/// ends_operand
+///
+/// # Arguments
+/// * `atom`: &Atom - the atom to check
+///
+/// # Returns
+/// * `bool` - whether the atom can end an operand, making a following operator binary
+fn ends_operand(atom: &Atom) -> bool {
+    match atom {
+        Atom::Word(word) => !NON_OPERAND_KEYWORDS.contains(&word.as_str()),
+        Atom::Close(_) | Atom::GroupClose => true,
+        Atom::Lifetime(_) | Atom::Op(_) | Atom::Open(_) | Atom::GroupOpen => false,
+    }
+}
+
+/// needs_space
+///
+/// # Arguments
+/// * `before`: Option<&Atom> - the atom printed before `prev`, if any
+/// * `prev`: &Atom - the atom already printed
+/// * `next`: &Atom - the atom about to be printed
+///
+/// # Returns
+/// * `bool` - whether a space separates the two atoms
+fn needs_space(before: Option<&Atom>, prev: &Atom, next: &Atom) -> bool {
+    let is_op =
+        |atom: &Atom, ops: &[&str]| matches!(atom, Atom::Op(op) if ops.contains(&op.as_str()));
+
+    match (prev, next) {
+        (Atom::GroupOpen, _) | (_, Atom::GroupClose) => false,
+        (Atom::Open('(' | '['), _) | (_, Atom::Close(')' | ']')) => false,
+        (Atom::Open('{'), _) | (_, Atom::Close('}')) => true,
+        (_, next) if is_op(next, &[",", ";"]) => false,
+        (prev, _) if is_op(prev, &[",", ";"]) => true,
+        (prev, next) if is_op(prev, &["::"]) || is_op(next, &["::"]) => false,
+        (prev, next)
+            if is_op(prev, &["+", "=", "->", "=>"]) || is_op(next, &["+", "=", "->", "=>"]) =>
+        {
+            true
+        }
+        (_, next) if is_op(next, &[":"]) => false,
+        (prev, _) if is_op(prev, &[":"]) => true,
+        (prev, next) if is_op(prev, &["<"]) || is_op(next, &["<", ">"]) => false,
+        (prev, _) if is_op(prev, &PREFIX_OPS) && !before.is_some_and(ends_operand) => false,
+        (Atom::Word(_), next) if is_op(next, &["!"]) => false,
+        (Atom::Word(word), Atom::Open('(' | '[')) => SPACED_KEYWORDS.contains(&word.as_str()),
+        (Atom::Close(_), Atom::Open('(' | '[')) => false,
+        (prev, Atom::Open('(' | '[')) if is_op(prev, &[">"]) => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{Type, TypeParamBound, parse_quote};
+
+    #[test]
+    fn test_pretty_types() {
+        let cases: Vec<(Type, &str)> = vec![
+            (parse_quote!(Vec<String>), "Vec<String>"),
+            (parse_quote!(Vec<Vec<u8>>), "Vec<Vec<u8>>"),
+            (
+                parse_quote!(HashMap<K, &'a mut str>),
+                "HashMap<K, &'a mut str>",
+            ),
+            (parse_quote!(&&str), "&&str"),
+            (parse_quote!(&'a [T]), "&'a [T]"),
+            (parse_quote!([u8; 4]), "[u8; 4]"),
+            (parse_quote!((u8, u8)), "(u8, u8)"),
+            (parse_quote!(*const T), "*const T"),
+            (parse_quote!(fn(u8) -> bool), "fn(u8) -> bool"),
+            (
+                parse_quote!(extern "C" fn(i32, ...)),
+                "extern \"C\" fn(i32, ...)",
+            ),
+            (
+                parse_quote!(Box<dyn for<'a> Fn(&'a u8) -> () + Send>),
+                "Box<dyn for<'a> Fn(&'a u8) -> () + Send>",
+            ),
+            (parse_quote!(<T as Trait>::Output), "<T as Trait>::Output"),
+            (
+                parse_quote!(impl Iterator<Item = u8> + 'static),
+                "impl Iterator<Item = u8> + 'static",
+            ),
+            (parse_quote!([T; { N + 1 }]), "[T; { N + 1 }]"),
+            (parse_quote!([u8; 2 - 1]), "[u8; 2 - 1]"),
+            (parse_quote!([u8; N * 2]), "[u8; N * 2]"),
+            (parse_quote!([u8; -1]), "[u8; -1]"),
+            (parse_quote!(Foo<{ N - 1 }>), "Foo<{ N - 1 }>"),
+            (parse_quote!([u8; (N) & !M]), "[u8; (N) & !M]"),
+            (parse_quote!(&mut *const T), "&mut *const T"),
+            (
+                parse_quote!([u8; { 1 >= 0 } as usize]),
+                "[u8; { 1 >= 0 } as usize]",
+            ),
+        ];
+
+        for (ty, expected) in cases {
+            assert_eq!(pretty_tokens(&ty), expected);
+        }
+    }
+
+    #[test]
+    fn test_pretty_bounds() {
+        let bound: TypeParamBound = parse_quote!(?Sized);
+        assert_eq!(pretty_tokens(&bound), "?Sized");
+
+        let bound: TypeParamBound = parse_quote!(Into<std::string::String>);
+        assert_eq!(pretty_tokens(&bound), "Into<std::string::String>");
+    }
+
+    #[test]
+    fn test_pretty_operators() {
+        let operators = [
+            "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=",
+            "<<=", ">>=",
+        ];
+        for operator in operators {
+            let source = format!("a {} b", operator);
+            let expr: syn::Expr = syn::parse_str(&source).unwrap();
+            assert_eq!(pretty_tokens(&expr), source);
+        }
+
+        // `<`, `>`, `<<` and `>>` may lose their spaces, but must keep their meaning
+        for operator in ["<", ">", "<<", ">>"] {
+            let expr: syn::Expr = syn::parse_str(&format!("a {} b", operator)).unwrap();
+            let reparsed: syn::Expr = syn::parse_str(&pretty_tokens(&expr)).unwrap();
+            assert_eq!(
+                reparsed.to_token_stream().to_string(),
+                expr.to_token_stream().to_string()
+            );
+        }
+
+        // tokens taken from the source, as in attribute arguments, are joined the same way
+        let attr: syn::Attribute = parse_quote!(#[my(a >= 1, b == 2, c <<= 3)]);
+        let syn::Meta::List(list) = attr.meta else {
+            panic!("expected a list attribute");
+        };
+        assert_eq!(pretty_tokens(&list.tokens), "a >= 1, b == 2, c <<= 3");
+    }
+
+    #[test]
+    fn test_pretty_paths() {
+        let tree: syn::UseTree = parse_quote!(std::fmt::{self, Write});
+        assert_eq!(pretty_tokens(&tree), "std::fmt::{self, Write}");
+
+        let tree: syn::UseTree = parse_quote!(a::{b::{c, d as e}, f::*});
+        assert_eq!(pretty_tokens(&tree), "a::{b::{c, d as e}, f::*}");
+
+        let path: syn::Path = parse_quote!(std::mem::drop::<u8>);
+        assert_eq!(pretty_tokens(&path), "std::mem::drop::<u8>");
+    }
+}
@@ -1,20 +1,37 @@
-use quote::ToTokens;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::Path;
 
-use syn::{File, visit::Visit};
+use syn::File;
 
-/// TextVisitor is a visitor that prints the AST in a text format
+use crate::builder::{build_expr, build_item, build_item_fn, build_stmt};
+use crate::model::{
+    AstJson, AttributeJson, ExprJson, FieldJson, FieldsKind, FragmentJson, GenericParamJson,
+    GenericsJson, ItemJson, Node, PatternJson, SpanJson, StmtJson, VisibilityJson,
+    WherePredicateJson,
+};
+
+/// TextVisitor is a visitor that writes the AST in a text format
+///
+/// The syntax tree is first converted into the shared `AstJson` model, which is
+/// then rendered as indented text into any `io::Write` sink (stdout by default).
 ///
 /// # Fields
+/// * `out`: W - the sink the text is written to
 /// * `indent`: usize - the current indentation level
-pub struct TextVisitor {
+/// * `show_spans`: bool - whether to print the source range of every node
+/// * `error`: Option<io::Error> - the first write error hit while visiting
+pub struct TextVisitor<W: Write = io::Stdout> {
+    out: W,
     indent: usize,
+    show_spans: bool,
+    error: Option<io::Error>,
 }
 
 /// # Methods
-/// * `new()`: creates a new TextVisitor
+/// * `new()`: creates a new TextVisitor writing to stdout
+/// * `with_writer()`: creates a new TextVisitor writing to any sink
+/// * `with_spans()`: enables or disables source ranges in the output
 /// * `print_indent()`: prints the current indentation level
 impl Default for TextVisitor {
     fn default() -> Self {
@@ -29,9 +46,92 @@ impl TextVisitor {
     /// * `()`
     ///
     /// # Returns
-    /// * `TextVisitor` - a new TextVisitor
+    /// * `TextVisitor` - a new TextVisitor writing to stdout
     pub fn new() -> Self {
-        TextVisitor { indent: 0 }
+        TextVisitor::with_writer(io::stdout())
+    }
+}
+
+impl<W: Write> TextVisitor<W> {
+    /// with_writer
+    ///
+    /// # Arguments
+    /// * `out`: W - the sink to write the text to
+    ///
+    /// # Returns
+    /// * `TextVisitor<W>` - a new TextVisitor
+    pub fn with_writer(out: W) -> Self {
+        TextVisitor {
+            out,
+            indent: 0,
+            show_spans: false,
+            error: None,
+        }
+    }
+
+    /// into_inner
+    ///
+    /// # Arguments
+    /// * `self`: Self - the TextVisitor
+    ///
+    /// # Returns
+    /// * `W` - the sink the text was written to
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// take_error
+    ///
+    /// `syn::visit::Visit` methods cannot return errors, so the first write error
+    /// hit while visiting is kept here until it is taken.
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    ///
+    /// # Returns
+    /// * `Option<io::Error>` - the first write error, if any
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// record
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `result`: io::Result<()> - the result of rendering a visited node
+    ///
+    /// # Returns
+    /// * `()`
+    fn record(&mut self, result: io::Result<()>) {
+        if let Err(err) = result {
+            self.error.get_or_insert(err);
+        }
+    }
+
+    /// render_file
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `file`: &File - the file to render
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    pub fn render_file(&mut self, file: &File) -> io::Result<()> {
+        self.render_ast(&AstJson::from_file(file))?;
+        self.out.flush()
+    }
+
+    /// with_spans
+    ///
+    /// # Arguments
+    /// * `self`: Self - the TextVisitor
+    /// * `show_spans`: bool - whether to print the source range of every node
+    ///
+    /// # Returns
+    /// * `TextVisitor<W>` - the updated TextVisitor
+    pub fn with_spans(mut self, show_spans: bool) -> Self {
+        self.show_spans = show_spans;
+        self
     }
 
     /// print_indent
@@ -44,220 +144,1564 @@ impl TextVisitor {
     fn print_indent(&self) -> String {
         " ".repeat(self.indent)
     }
-}
 
-/// implement Visit trait for AstText
-/// Visit trait is defined in syn::visit
-impl<'ast> syn::visit::Visit<'ast> for TextVisitor {
-    /// visit_item_fn is defined in syn::visit::Visit
-    /// visit_item_fn is called when a Rust function definition is visited
+    /// print_span
     ///
     /// # Arguments
-    /// * `node`: &'ast syn::ItemFn
+    /// * `self`: &Self - the TextVisitor
+    /// * `span`: &Option<SpanJson> - the source range of a node
     ///
     /// # Returns
-    /// * `()`
-    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
-        println!("{}Function: {}", self.print_indent(), node.sig.ident);
-        self.indent += 2;
+    /// * `String` - a ` [line:column-line:column]` suffix, or an empty string when spans are hidden
+    fn print_span(&self, span: &Option<SpanJson>) -> String {
+        match span {
+            Some(span) if self.show_spans => format!(
+                " [{}:{}-{}:{}]",
+                span.start.line, span.start.column, span.end.line, span.end.column
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// print_label
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the TextVisitor
+    /// * `label`: &Option<String> - the label of a loop, block, `break` or `continue`
+    ///
+    /// # Returns
+    /// * `String` - a ` 'label` suffix, or an empty string when there is no label
+    fn print_label(&self, label: &Option<String>) -> String {
+        match label {
+            Some(label) => format!(" {}", label),
+            None => String::new(),
+        }
+    }
+
+    /// print_visibility
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the TextVisitor
+    /// * `visibility`: &Option<VisibilityJson> - the visibility of an item or field
+    ///
+    /// # Returns
+    /// * `String` - a `pub ` style prefix, or an empty string for a private item or field
+    fn print_visibility(&self, visibility: &Option<VisibilityJson>) -> String {
+        match visibility {
+            Some(VisibilityJson::Public) => "pub ".to_string(),
+            Some(VisibilityJson::Crate) => "pub(crate) ".to_string(),
+            Some(VisibilityJson::Restricted { path }) if path == "self" || path == "super" => {
+                format!("pub({}) ", path)
+            }
+            Some(VisibilityJson::Restricted { path }) => format!("pub(in {}) ", path),
+            None => String::new(),
+        }
+    }
+
+    /// print_suffix
+    ///
+    /// # Arguments
+    /// * `self`: &Self - the TextVisitor
+    /// * `suffix`: &Option<String> - the type suffix of a numeric literal
+    ///
+    /// # Returns
+    /// * `String` - a ` - Suffix: u8` suffix, or an empty string when the literal has none
+    fn print_suffix(&self, suffix: &Option<String>) -> String {
+        match suffix {
+            Some(suffix) => format!(" - Suffix: {}", suffix),
+            None => String::new(),
+        }
+    }
+
+    /// render_attributes
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `attributes`: &[AttributeJson] - the attributes of a node
+    /// * `docs`: &Option<String> - the doc comments of a node
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_attributes(
+        &mut self,
+        attributes: &[AttributeJson],
+        docs: &Option<String>,
+    ) -> io::Result<()> {
+        if let Some(docs) = docs {
+            for line in docs.lines() {
+                writeln!(self.out, "{}Docs: {}", self.print_indent(), line)?;
+            }
+        }
+
+        // doc comments are kept among the attributes too, and were printed above
+        for attribute in attributes.iter().filter(|attr| attr.doc.is_none()) {
+            let mut text = attribute.path.clone();
+            if let Some(arguments) = &attribute.arguments {
+                text = format!("{}({})", text, arguments);
+            } else if let Some(value) = &attribute.value {
+                text = format!("{} = {}", text, value);
+            }
+            writeln!(
+                self.out,
+                "{}Attribute: #{}[{}]",
+                self.print_indent(),
+                if attribute.inner { "!" } else { "" },
+                text
+            )?;
+        }
+        Ok(())
+    }
+
+    /// render_ast
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `ast`: &AstJson - the model to render
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    pub fn render_ast(&mut self, ast: &AstJson) -> io::Result<()> {
+        self.render_attributes(&ast.attributes, &ast.docs)?;
+        for item in &ast.items {
+            self.render_item(item)?;
+        }
+        Ok(())
+    }
+
+    /// render_fragment
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `fragment`: &FragmentJson - the fragment to render
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    pub fn render_fragment(&mut self, fragment: &FragmentJson) -> io::Result<()> {
+        match fragment {
+            FragmentJson::Expression { expr } => self.render_expr(expr)?,
+            FragmentJson::Type {
+                type_info,
+                type_tree,
+            } => {
+                let span = self.print_span(&type_tree.span);
+                writeln!(
+                    self.out,
+                    "{}Type: {}{}",
+                    self.print_indent(),
+                    type_info,
+                    span
+                )?;
+            }
+            FragmentJson::Item { item } => self.render_item(item)?,
+            FragmentJson::Statement { stmt } => self.render_stmt(stmt)?,
+            FragmentJson::Pattern { pattern } => {
+                let span = self.print_span(&pattern.span);
+                writeln!(
+                    self.out,
+                    "{}Pattern: {}{}",
+                    self.print_indent(),
+                    format_pattern(&pattern.kind),
+                    span
+                )?;
+            }
+            FragmentJson::Block { body } => {
+                writeln!(self.out, "{}Block:", self.print_indent())?;
+                self.render_block(body)?;
+            }
+            FragmentJson::Attributes { attributes, docs } => {
+                self.render_attributes(attributes, docs)?;
+            }
+        }
+        self.out.flush()
+    }
+
+    /// render_item
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `item`: &Node<ItemJson> - the item to render
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_item(&mut self, item: &Node<ItemJson>) -> io::Result<()> {
+        let span = self.print_span(&item.span);
+        self.render_attributes(&item.attributes, &item.docs)?;
+        match &item.kind {
+            ItemJson::Function {
+                name,
+                visibility,
+                is_const,
+                is_async,
+                is_unsafe,
+                is_extern,
+                abi,
+                generics,
+                parameters,
+                variadic,
+                return_type,
+                body,
+                ..
+            } => {
+                let mut qualifiers = self.print_visibility(visibility);
+                if *is_const {
+                    qualifiers.push_str("const ");
+                }
+                if *is_async {
+                    qualifiers.push_str("async ");
+                }
+                if *is_unsafe {
+                    qualifiers.push_str("unsafe ");
+                }
+                if *is_extern {
+                    match abi {
+                        Some(abi) => qualifiers.push_str(&format!("extern {:?} ", abi)),
+                        None => qualifiers.push_str("extern "),
+                    }
+                }
+                writeln!(
+                    self.out,
+                    "{}Function: {}{}{}",
+                    self.print_indent(),
+                    qualifiers,
+                    name,
+                    span
+                )?;
+                self.indent += 2;
+                self.render_generics(generics)?;
+
+                if !parameters.is_empty() || *variadic {
+                    writeln!(self.out, "{}Parameters:", self.print_indent())?;
+                    self.indent += 2;
+                    for param in parameters {
+                        let param_span = self.print_span(&param.span);
+                        self.render_attributes(&param.attributes, &None)?;
+                        if param.name == "self" {
+                            writeln!(
+                                self.out,
+                                "{}Self receiver: {}{}",
+                                self.print_indent(),
+                                param.type_info,
+                                param_span
+                            )?;
+                        } else {
+                            writeln!(
+                                self.out,
+                                "{}Parameter: {} - Type: {}{}",
+                                self.print_indent(),
+                                param.pattern.as_ref().map_or_else(
+                                    || param.name.clone(),
+                                    |p| format_pattern(&p.kind)
+                                ),
+                                param.type_info,
+                                param_span
+                            )?;
+                        }
+                    }
+                    if *variadic {
+                        writeln!(self.out, "{}Variadic: ...", self.print_indent())?;
+                    }
+                    self.indent -= 2;
+                }
+
+                if let Some(return_type) = return_type {
+                    writeln!(
+                        self.out,
+                        "{}Return type: {}",
+                        self.print_indent(),
+                        return_type
+                    )?;
+                }
+
+                if let Some(body) = body {
+                    writeln!(self.out, "{}Body:", self.print_indent())?;
+                    self.render_block(body)?;
+                }
+                self.indent -= 2;
+            }
+            ItemJson::Struct {
+                name,
+                visibility,
+                generics,
+                kind,
+                fields,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Struct: {}{}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    match kind {
+                        FieldsKind::Named => "",
+                        FieldsKind::Tuple => " (tuple)",
+                        FieldsKind::Unit => " (unit)",
+                    },
+                    span
+                )?;
+                self.render_generics(generics)?;
+                self.render_fields(fields)?;
+            }
+            ItemJson::Enum {
+                name,
+                visibility,
+                generics,
+                variants,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Enum: {}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    span
+                )?;
+                self.render_generics(generics)?;
+                if !variants.is_empty() {
+                    writeln!(self.out, "{}Variants:", self.print_indent())?;
+                    self.indent += 2;
+                    for variant in variants {
+                        self.render_attributes(&variant.attributes, &variant.docs)?;
+                        writeln!(
+                            self.out,
+                            "{}Variant: {}{}",
+                            self.print_indent(),
+                            variant.name,
+                            self.print_span(&variant.span)
+                        )?;
+                        self.indent += 2;
+                        self.render_fields(&variant.fields)?;
+                        if let Some(discriminant) = &variant.discriminant {
+                            writeln!(self.out, "{}Discriminant:", self.print_indent())?;
+                            self.render_nested_expr(discriminant)?;
+                        }
+                        self.indent -= 2;
+                    }
+                    self.indent -= 2;
+                }
+            }
+            ItemJson::Union {
+                name,
+                visibility,
+                generics,
+                fields,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Union: {}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    span
+                )?;
+                self.render_generics(generics)?;
+                self.render_fields(fields)?;
+            }
+            ItemJson::Impl {
+                generics,
+                is_default,
+                is_unsafe,
+                negative,
+                self_type,
+                trait_name,
+                items,
+                ..
+            } => {
+                let qualifier = match (*is_default, *is_unsafe) {
+                    (true, true) => "default unsafe ",
+                    (true, false) => "default ",
+                    (false, true) => "unsafe ",
+                    (false, false) => "",
+                };
+                match trait_name {
+                    Some(trait_name) => writeln!(
+                        self.out,
+                        "{}Impl: {}{}{} for {}{}",
+                        self.print_indent(),
+                        qualifier,
+                        if *negative { "!" } else { "" },
+                        trait_name,
+                        self_type,
+                        span
+                    )?,
+                    None => writeln!(
+                        self.out,
+                        "{}Impl: {}{}{}",
+                        self.print_indent(),
+                        qualifier,
+                        self_type,
+                        span
+                    )?,
+                }
+                self.render_generics(generics)?;
+                self.render_items(items)?;
+            }
+            ItemJson::Trait {
+                name,
+                visibility,
+                generics,
+                is_unsafe,
+                is_auto,
+                supertraits,
+                items,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Trait: {}{}{}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    if *is_unsafe { "unsafe " } else { "" },
+                    if *is_auto { "auto " } else { "" },
+                    name,
+                    span
+                )?;
+                self.render_generics(generics)?;
+                if !supertraits.is_empty() {
+                    writeln!(
+                        self.out,
+                        "{}Supertraits: {}",
+                        self.print_indent(),
+                        supertraits.join(" + ")
+                    )?;
+                }
+                self.render_items(items)?;
+            }
+            ItemJson::Module {
+                name,
+                visibility,
+                is_unsafe,
+                inline,
+                items,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Module: {}{}{}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    if *is_unsafe { "unsafe " } else { "" },
+                    name,
+                    if *inline { "" } else { " (out-of-line)" },
+                    span
+                )?;
+                if let Some(items) = items {
+                    self.render_items(items)?;
+                }
+            }
+            ItemJson::Use {
+                visibility,
+                leading_colon,
+                tree,
+                paths,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Use: {}{}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    if *leading_colon { ":: " } else { "" },
+                    tree,
+                    span
+                )?;
+                if !paths.is_empty() {
+                    writeln!(self.out, "{}Paths:", self.print_indent())?;
+                    self.indent += 2;
+                    for path in paths {
+                        let mut text = path.path.clone();
+                        if path.glob {
+                            text.push_str("::*");
+                        }
+                        if let Some(alias) = &path.alias {
+                            text = format!("{} as {}", text, alias);
+                        }
+                        writeln!(self.out, "{}Path: {}", self.print_indent(), text)?;
+                    }
+                    self.indent -= 2;
+                }
+            }
+            ItemJson::ExternCrate {
+                name,
+                visibility,
+                alias,
+            } => match alias {
+                Some(alias) => writeln!(
+                    self.out,
+                    "{}Extern crate: {}{} as {}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    alias,
+                    span
+                )?,
+                None => writeln!(
+                    self.out,
+                    "{}Extern crate: {}{}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    span
+                )?,
+            },
+            ItemJson::Const {
+                name,
+                visibility,
+                type_info,
+                value,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Const: {}{} - Type: {}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    name,
+                    type_info,
+                    span
+                )?;
+                if let Some(value) = value {
+                    writeln!(self.out, "{}Value:", self.print_indent())?;
+                    self.render_nested_expr(value)?;
+                }
+            }
+            ItemJson::Static {
+                name,
+                visibility,
+                mutable,
+                type_info,
+                value,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Static: {}{}{} - Type: {}{}",
+                    self.print_indent(),
+                    self.print_visibility(visibility),
+                    if *mutable { "mut " } else { "" },
+                    name,
+                    type_info,
+                    span
+                )?;
+                if let Some(value) = value {
+                    writeln!(self.out, "{}Value:", self.print_indent())?;
+                    self.render_nested_expr(value)?;
+                }
+            }
+            ItemJson::TypeAlias {
+                name,
+                visibility,
+                generics,
+                bounds,
+                type_info,
+                ..
+            } => {
+                match type_info {
+                    Some(type_info) => writeln!(
+                        self.out,
+                        "{}Type alias: {}{} = {}{}",
+                        self.print_indent(),
+                        self.print_visibility(visibility),
+                        name,
+                        type_info,
+                        span
+                    )?,
+                    None => writeln!(
+                        self.out,
+                        "{}Type alias: {}{}{}",
+                        self.print_indent(),
+                        self.print_visibility(visibility),
+                        name,
+                        span
+                    )?,
+                }
+                self.render_generics(generics)?;
+                if !bounds.is_empty() {
+                    writeln!(
+                        self.out,
+                        "{}Bounds: {}",
+                        self.print_indent(),
+                        bounds.join(" + ")
+                    )?;
+                }
+            }
+            ItemJson::Macro {
+                name, path, tokens, ..
+            } => {
+                match name {
+                    Some(name) => writeln!(
+                        self.out,
+                        "{}Macro: {}! {}{}",
+                        self.print_indent(),
+                        path,
+                        name,
+                        span
+                    )?,
+                    None => writeln!(self.out, "{}Macro: {}!{}", self.print_indent(), path, span)?,
+                }
+                writeln!(self.out, "{}Tokens: {}", self.print_indent(), tokens)?;
+            }
+            ItemJson::MacroRules { name, rules } => {
+                writeln!(
+                    self.out,
+                    "{}Macro rules: {}{}",
+                    self.print_indent(),
+                    name,
+                    span
+                )?;
+                if !rules.is_empty() {
+                    writeln!(self.out, "{}Rules:", self.print_indent())?;
+                    self.indent += 2;
+                    for rule in rules {
+                        writeln!(self.out, "{}Rule:", self.print_indent())?;
+                        self.indent += 2;
+                        writeln!(self.out, "{}Matcher: {}", self.print_indent(), rule.matcher)?;
+                        writeln!(
+                            self.out,
+                            "{}Transcriber: {}",
+                            self.print_indent(),
+                            rule.transcriber
+                        )?;
+                        self.indent -= 2;
+                    }
+                    self.indent -= 2;
+                }
+            }
+            ItemJson::ExternBlock {
+                is_unsafe,
+                abi,
+                items,
+            } => {
+                let mut header = String::from("Extern block:");
+                if *is_unsafe {
+                    header.push_str(" unsafe");
+                }
+                if let Some(abi) = abi {
+                    header = format!("{} \"{}\"", header, abi);
+                }
+                writeln!(self.out, "{}{}{}", self.print_indent(), header, span)?;
+                self.render_items(items)?;
+            }
+            ItemJson::Other { description } => {
+                writeln!(
+                    self.out,
+                    "{}Other item: {}{}",
+                    self.print_indent(),
+                    description,
+                    span
+                )?;
+            }
+        }
+        Ok(())
+    }
 
-        if !node.sig.inputs.is_empty() {
-            println!("{}Parameters:", self.print_indent());
+    /// render_items
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `items`: &[Node<ItemJson>] - the items nested in an impl, trait, module or extern block
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_items(&mut self, items: &[Node<ItemJson>]) -> io::Result<()> {
+        if !items.is_empty() {
+            writeln!(self.out, "{}Items:", self.print_indent())?;
             self.indent += 2;
-            for param in &node.sig.inputs {
+            for item in items {
+                self.render_item(item)?;
+            }
+            self.indent -= 2;
+        }
+        Ok(())
+    }
+
+    /// render_generics
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `generics`: &GenericsJson - the generic parameters and where clause of an item
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_generics(&mut self, generics: &GenericsJson) -> io::Result<()> {
+        if !generics.params.is_empty() {
+            writeln!(self.out, "{}Generics:", self.print_indent())?;
+            self.indent += 2;
+            for param in &generics.params {
                 match param {
-                    syn::FnArg::Typed(pat_type) => {
-                        if let syn::Pat::Ident(pat_ident) = &*pat_type.pat {
-                            println!(
-                                "{}Parameter: {} - Type: {}",
-                                self.print_indent(),
-                                pat_ident.ident,
-                                pat_type.ty.to_token_stream()
-                            );
+                    GenericParamJson::Type {
+                        name,
+                        bounds,
+                        default,
+                    } => {
+                        let mut text = name.clone();
+                        if !bounds.is_empty() {
+                            text = format!("{}: {}", text, bounds.join(" + "));
+                        }
+                        if let Some(default) = default {
+                            text = format!("{} = {}", text, default);
                         }
+                        writeln!(self.out, "{}Type parameter: {}", self.print_indent(), text)?;
                     }
-                    syn::FnArg::Receiver(receiver) => {
-                        println!(
-                            "{}Self receiver: {}",
+                    GenericParamJson::Lifetime { name, bounds } => {
+                        let mut text = name.clone();
+                        if !bounds.is_empty() {
+                            text = format!("{}: {}", text, bounds.join(" + "));
+                        }
+                        writeln!(
+                            self.out,
+                            "{}Lifetime parameter: {}",
                             self.print_indent(),
-                            receiver.to_token_stream()
-                        );
+                            text
+                        )?;
+                    }
+                    GenericParamJson::Const {
+                        name,
+                        type_info,
+                        default,
+                    } => {
+                        let mut text = format!("{} - Type: {}", name, type_info);
+                        if let Some(default) = default {
+                            text = format!("{} = {}", text, default);
+                        }
+                        writeln!(self.out, "{}Const parameter: {}", self.print_indent(), text)?;
                     }
                 }
             }
             self.indent -= 2;
         }
 
-        if let syn::ReturnType::Type(_, return_type) = &node.sig.output {
-            println!(
-                "{}Return type: {}",
-                self.print_indent(),
-                return_type.to_token_stream()
-            );
+        if !generics.where_clause.is_empty() {
+            writeln!(self.out, "{}Where clause:", self.print_indent())?;
+            self.indent += 2;
+            for predicate in &generics.where_clause {
+                let text = match predicate {
+                    WherePredicateJson::Type {
+                        lifetimes,
+                        bounded_type,
+                        bounds,
+                    } if lifetimes.is_empty() => {
+                        format!("{}: {}", bounded_type, bounds.join(" + "))
+                    }
+                    WherePredicateJson::Type {
+                        lifetimes,
+                        bounded_type,
+                        bounds,
+                    } => format!(
+                        "for<{}> {}: {}",
+                        lifetimes.join(", "),
+                        bounded_type,
+                        bounds.join(" + ")
+                    ),
+                    WherePredicateJson::Lifetime { lifetime, bounds } => {
+                        format!("{}: {}", lifetime, bounds.join(" + "))
+                    }
+                };
+                writeln!(self.out, "{}Predicate: {}", self.print_indent(), text)?;
+            }
+            self.indent -= 2;
         }
+        Ok(())
+    }
 
-        println!("{}Body:", self.print_indent());
+    /// render_fields
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `fields`: &[FieldJson] - the fields of a struct or union
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_fields(&mut self, fields: &[FieldJson]) -> io::Result<()> {
+        if !fields.is_empty() {
+            writeln!(self.out, "{}Fields:", self.print_indent())?;
+            self.indent += 2;
+            for field in fields {
+                let field_span = self.print_span(&field.span);
+                self.render_attributes(&field.attributes, &field.docs)?;
+                if let Some(field_name) = &field.name {
+                    writeln!(
+                        self.out,
+                        "{}Field: {}{} - Type: {}{}",
+                        self.print_indent(),
+                        self.print_visibility(&field.visibility),
+                        field_name,
+                        field.type_info,
+                        field_span
+                    )?;
+                } else {
+                    writeln!(
+                        self.out,
+                        "{}Tuple field {}: {}{}{}",
+                        self.print_indent(),
+                        field.index.unwrap_or_default(),
+                        self.print_visibility(&field.visibility),
+                        field.type_info,
+                        field_span
+                    )?;
+                }
+            }
+            self.indent -= 2;
+        }
+        Ok(())
+    }
+
+    /// render_block
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `stmts`: &[Node<StmtJson>] - the statements to render, one level deeper
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_block(&mut self, stmts: &[Node<StmtJson>]) -> io::Result<()> {
         self.indent += 2;
-        for stmt in &node.block.stmts {
-            self.visit_stmt(stmt);
+        for stmt in stmts {
+            self.render_stmt(stmt)?;
         }
         self.indent -= 2;
+        Ok(())
     }
 
-    /// visit_expr is defined in syn::visit::Visit
-    /// visit_expr is called when a Rust expression is visited
+    /// render_stmt
     ///
     /// # Arguments
-    /// * `node`: &'ast syn::Expr
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `stmt`: &Node<StmtJson> - the statement to render
     ///
     /// # Returns
-    /// * `()`
-    fn visit_expr(&mut self, node: &'ast syn::Expr) {
-        match node {
-            syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
-                syn::Lit::Int(lit_int) => {
-                    println!(
-                        "{}Integer literal: {}",
+    /// * `io::Result<()>` - result
+    fn render_stmt(&mut self, stmt: &Node<StmtJson>) -> io::Result<()> {
+        let span = self.print_span(&stmt.span);
+        self.render_attributes(&stmt.attributes, &stmt.docs)?;
+        match &stmt.kind {
+            StmtJson::VariableDeclaration {
+                name,
+                pattern,
+                type_info,
+                initializer,
+                else_branch,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Variable declaration:{}",
+                    self.print_indent(),
+                    span
+                )?;
+                match name {
+                    Some(name) => writeln!(self.out, "{}Name: {}", self.print_indent(), name)?,
+                    None => writeln!(
+                        self.out,
+                        "{}Pattern: {}",
                         self.print_indent(),
-                        lit_int.base10_digits()
-                    );
+                        format_pattern(&pattern.kind)
+                    )?,
                 }
-                syn::Lit::Float(lit_float) => {
-                    println!(
-                        "{}Float literal: {}",
-                        self.print_indent(),
-                        lit_float.base10_digits()
-                    );
+
+                if let Some(type_info) = type_info {
+                    writeln!(self.out, "{}Type: {}", self.print_indent(), type_info)?;
                 }
-                syn::Lit::Str(lit_str) => {
-                    println!(
-                        "{}String literal: \"{}\"",
-                        self.print_indent(),
-                        lit_str.value()
-                    );
+
+                if let Some(init) = initializer {
+                    writeln!(self.out, "{}Initializer:", self.print_indent())?;
+                    self.render_nested_expr(init)?;
                 }
-                syn::Lit::Bool(lit_bool) => {
-                    println!("{}Boolean literal: {}", self.print_indent(), lit_bool.value);
+
+                if let Some(else_branch) = else_branch {
+                    writeln!(self.out, "{}Else branch:", self.print_indent())?;
+                    self.render_nested_expr(else_branch)?;
                 }
-                _ => {
-                    println!(
-                        "{}Other literal: {}",
-                        self.print_indent(),
-                        expr_lit.to_token_stream()
-                    );
+            }
+            StmtJson::Expression { expr, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Expression statement:{}",
+                    self.print_indent(),
+                    span
+                )?;
+                self.render_nested_expr(expr)?;
+            }
+            StmtJson::Item { item } => {
+                self.render_item(item)?;
+            }
+            StmtJson::Other { description } => {
+                writeln!(
+                    self.out,
+                    "{}Other statement: {}{}",
+                    self.print_indent(),
+                    description,
+                    span
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// render_nested_expr
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `expr`: &Node<ExprJson> - the expression to render, one level deeper
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_nested_expr(&mut self, expr: &Node<ExprJson>) -> io::Result<()> {
+        self.indent += 2;
+        self.render_expr(expr)?;
+        self.indent -= 2;
+        Ok(())
+    }
+
+    /// render_expr
+    ///
+    /// # Arguments
+    /// * `self`: &mut Self - the TextVisitor
+    /// * `expr`: &Node<ExprJson> - the expression to render
+    ///
+    /// # Returns
+    /// * `io::Result<()>` - result
+    fn render_expr(&mut self, expr: &Node<ExprJson>) -> io::Result<()> {
+        let span = self.print_span(&expr.span);
+        self.render_attributes(&expr.attributes, &expr.docs)?;
+        match &expr.kind {
+            ExprJson::IntLiteral { value, suffix, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Integer literal: {}{}{}",
+                    self.print_indent(),
+                    value,
+                    self.print_suffix(suffix),
+                    span
+                )?;
+            }
+            ExprJson::FloatLiteral { value, suffix, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Float literal: {}{}{}",
+                    self.print_indent(),
+                    value,
+                    self.print_suffix(suffix),
+                    span
+                )?;
+            }
+            ExprJson::StringLiteral { value, .. } => {
+                writeln!(
+                    self.out,
+                    "{}String literal: \"{}\"{}",
+                    self.print_indent(),
+                    value,
+                    span
+                )?;
+            }
+            ExprJson::BoolLiteral { value } => {
+                writeln!(
+                    self.out,
+                    "{}Boolean literal: {}{}",
+                    self.print_indent(),
+                    value,
+                    span
+                )?;
+            }
+            ExprJson::CharLiteral { raw, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Char literal: {}{}",
+                    self.print_indent(),
+                    raw,
+                    span
+                )?;
+            }
+            ExprJson::ByteLiteral { raw, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Byte literal: {}{}",
+                    self.print_indent(),
+                    raw,
+                    span
+                )?;
+            }
+            ExprJson::ByteStringLiteral { raw, .. } => {
+                writeln!(
+                    self.out,
+                    "{}Byte string literal: {}{}",
+                    self.print_indent(),
+                    raw,
+                    span
+                )?;
+            }
+            ExprJson::CStringLiteral { raw, .. } => {
+                writeln!(
+                    self.out,
+                    "{}C string literal: {}{}",
+                    self.print_indent(),
+                    raw,
+                    span
+                )?;
+            }
+            ExprJson::VerbatimLiteral { raw } => {
+                writeln!(
+                    self.out,
+                    "{}Verbatim literal: {}{}",
+                    self.print_indent(),
+                    raw,
+                    span
+                )?;
+            }
+            ExprJson::StructLiteral { path, fields, base } => {
+                writeln!(
+                    self.out,
+                    "{}Struct literal: {}{}",
+                    self.print_indent(),
+                    path,
+                    span
+                )?;
+
+                if !fields.is_empty() {
+                    writeln!(self.out, "{}Fields:", self.print_indent())?;
+                    self.indent += 2;
+                    for field in fields {
+                        let field_span = self.print_span(&field.span);
+                        self.render_attributes(&field.attributes, &field.docs)?;
+                        writeln!(
+                            self.out,
+                            "{}Field: {}{}",
+                            self.print_indent(),
+                            field.member,
+                            field_span
+                        )?;
+                        self.render_nested_expr(&field.value)?;
+                    }
+                    self.indent -= 2;
                 }
-            },
-            syn::Expr::Binary(expr_bin) => {
-                let op = match expr_bin.op {
-                    syn::BinOp::Add(_) => "+",
-                    syn::BinOp::Sub(_) => "-",
-                    syn::BinOp::Mul(_) => "*",
-                    syn::BinOp::Div(_) => "/",
-                    syn::BinOp::Eq(_) => "==",
-                    syn::BinOp::Lt(_) => "<",
-                    syn::BinOp::Le(_) => "<=",
-                    syn::BinOp::Ne(_) => "!=",
-                    syn::BinOp::Ge(_) => ">=",
-                    syn::BinOp::Gt(_) => ">",
-                    _ => "other_operator",
-                };
-                println!("{}Binary expression: {}", self.print_indent(), op);
 
-                println!("{}Left:", self.print_indent());
-                self.indent += 2;
-                self.visit_expr(&expr_bin.left);
-                self.indent -= 2;
+                if let Some(base) = base {
+                    writeln!(self.out, "{}Base:", self.print_indent())?;
+                    self.render_nested_expr(base)?;
+                }
+            }
+            ExprJson::Tuple { elements } => {
+                writeln!(self.out, "{}Tuple:{}", self.print_indent(), span)?;
+                for element in elements {
+                    self.render_nested_expr(element)?;
+                }
+            }
+            ExprJson::Array { elements } => {
+                writeln!(self.out, "{}Array:{}", self.print_indent(), span)?;
+                for element in elements {
+                    self.render_nested_expr(element)?;
+                }
+            }
+            ExprJson::ArrayRepeat { value, length } => {
+                writeln!(self.out, "{}Array repeat:{}", self.print_indent(), span)?;
 
-                println!("{}Right:", self.print_indent());
-                self.indent += 2;
-                self.visit_expr(&expr_bin.right);
-                self.indent -= 2;
+                writeln!(self.out, "{}Value:", self.print_indent())?;
+                self.render_nested_expr(value)?;
+
+                writeln!(self.out, "{}Length:", self.print_indent())?;
+                self.render_nested_expr(length)?;
+            }
+            ExprJson::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Range: {}{}",
+                    self.print_indent(),
+                    if *inclusive { "..=" } else { ".." },
+                    span
+                )?;
+
+                if let Some(start) = start {
+                    writeln!(self.out, "{}Start:", self.print_indent())?;
+                    self.render_nested_expr(start)?;
+                }
+
+                if let Some(end) = end {
+                    writeln!(self.out, "{}End:", self.print_indent())?;
+                    self.render_nested_expr(end)?;
+                }
+            }
+            ExprJson::Parenthesized { expr: inner } => {
+                writeln!(self.out, "{}Parenthesized:{}", self.print_indent(), span)?;
+                self.render_nested_expr(inner)?;
             }
-            syn::Expr::Call(expr_call) => {
-                println!("{}Function call:", self.print_indent());
+            ExprJson::Binary {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Binary expression: {}{}",
+                    self.print_indent(),
+                    operator,
+                    span
+                )?;
 
-                println!("{}Function:", self.print_indent());
+                writeln!(self.out, "{}Left:", self.print_indent())?;
+                self.render_nested_expr(left)?;
+
+                writeln!(self.out, "{}Right:", self.print_indent())?;
+                self.render_nested_expr(right)?;
+            }
+            ExprJson::FunctionCall {
+                function,
+                arguments,
+            } => {
+                writeln!(self.out, "{}Function call:{}", self.print_indent(), span)?;
+
+                writeln!(self.out, "{}Function:", self.print_indent())?;
+                self.render_nested_expr(function)?;
+
+                if !arguments.is_empty() {
+                    writeln!(self.out, "{}Arguments:", self.print_indent())?;
+                    for arg in arguments {
+                        self.render_nested_expr(arg)?;
+                    }
+                }
+            }
+            ExprJson::Identifier { name } => {
+                writeln!(
+                    self.out,
+                    "{}Identifier: {}{}",
+                    self.print_indent(),
+                    name,
+                    span
+                )?;
+            }
+            ExprJson::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                writeln!(self.out, "{}If statement:{}", self.print_indent(), span)?;
+
+                writeln!(self.out, "{}Condition:", self.print_indent())?;
+                self.render_nested_expr(condition)?;
+
+                writeln!(self.out, "{}Then branch:", self.print_indent())?;
+                self.render_block(then_branch)?;
+
+                if let Some(else_branch) = else_branch {
+                    writeln!(self.out, "{}Else branch:", self.print_indent())?;
+                    self.render_nested_expr(else_branch)?;
+                }
+            }
+            ExprJson::Loop { label, body } => {
+                writeln!(
+                    self.out,
+                    "{}Loop:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+                self.render_block(body)?;
+            }
+            ExprJson::While {
+                label,
+                condition,
+                body,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}While loop:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+
+                writeln!(self.out, "{}Condition:", self.print_indent())?;
+                self.render_nested_expr(condition)?;
+
+                writeln!(self.out, "{}Body:", self.print_indent())?;
+                self.render_block(body)?;
+            }
+            ExprJson::ForLoop {
+                label,
+                pattern,
+                iterable,
+                body,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}For loop:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+                writeln!(
+                    self.out,
+                    "{}Pattern: {}",
+                    self.print_indent(),
+                    format_pattern(&pattern.kind)
+                )?;
+
+                writeln!(self.out, "{}Iterable:", self.print_indent())?;
+                self.render_nested_expr(iterable)?;
+
+                writeln!(self.out, "{}Body:", self.print_indent())?;
+                self.render_block(body)?;
+            }
+            ExprJson::Break { label, value } => {
+                writeln!(
+                    self.out,
+                    "{}Break:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+                if let Some(value) = value {
+                    self.render_nested_expr(value)?;
+                }
+            }
+            ExprJson::Continue { label } => {
+                writeln!(
+                    self.out,
+                    "{}Continue:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+            }
+            ExprJson::Return { value } => {
+                writeln!(self.out, "{}Return statement:{}", self.print_indent(), span)?;
+                if let Some(value) = value {
+                    self.render_nested_expr(value)?;
+                }
+            }
+            ExprJson::Let {
+                pattern,
+                expr: scrutinee,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Let condition: {}{}",
+                    self.print_indent(),
+                    format_pattern(&pattern.kind),
+                    span
+                )?;
+                self.render_nested_expr(scrutinee)?;
+            }
+            ExprJson::Try { expr: operand } => {
+                writeln!(self.out, "{}Try expression:{}", self.print_indent(), span)?;
+                self.render_nested_expr(operand)?;
+            }
+            ExprJson::Await { expr: operand } => {
+                writeln!(self.out, "{}Await expression:{}", self.print_indent(), span)?;
+                self.render_nested_expr(operand)?;
+            }
+            ExprJson::Match {
+                expr: scrutinee,
+                arms,
+            } => {
+                writeln!(self.out, "{}Match expression:{}", self.print_indent(), span)?;
+
+                writeln!(self.out, "{}Scrutinee:", self.print_indent())?;
+                self.render_nested_expr(scrutinee)?;
+
+                writeln!(self.out, "{}Arms:", self.print_indent())?;
                 self.indent += 2;
-                self.visit_expr(&expr_call.func);
+                for arm in arms {
+                    let arm_span = self.print_span(&arm.span);
+                    self.render_attributes(&arm.attributes, &arm.docs)?;
+                    writeln!(
+                        self.out,
+                        "{}Arm: {}{}",
+                        self.print_indent(),
+                        format_pattern(&arm.pattern.kind),
+                        arm_span
+                    )?;
+                    if let Some(guard) = &arm.guard {
+                        writeln!(self.out, "{}Guard:", self.print_indent())?;
+                        self.render_nested_expr(guard)?;
+                    }
+                    writeln!(self.out, "{}Body:", self.print_indent())?;
+                    self.render_nested_expr(&arm.body)?;
+                }
                 self.indent -= 2;
+            }
+            ExprJson::Closure {
+                lifetimes,
+                is_const,
+                is_static,
+                is_move,
+                is_async,
+                parameters,
+                return_type,
+                body,
+                ..
+            } => {
+                let mut header = String::from("Closure:");
+                if !lifetimes.is_empty() {
+                    header.push_str(&format!(" for<{}>", lifetimes.join(", ")));
+                }
+                if *is_const {
+                    header.push_str(" const");
+                }
+                if *is_static {
+                    header.push_str(" static");
+                }
+                if *is_move {
+                    header.push_str(" move");
+                }
+                if *is_async {
+                    header.push_str(" async");
+                }
+                writeln!(self.out, "{}{}{}", self.print_indent(), header, span)?;
 
-                if !expr_call.args.is_empty() {
-                    println!("{}Arguments:", self.print_indent());
+                if !parameters.is_empty() {
+                    writeln!(self.out, "{}Parameters:", self.print_indent())?;
                     self.indent += 2;
-                    for arg in &expr_call.args {
-                        self.visit_expr(arg);
+                    for param in parameters {
+                        let param_span = self.print_span(&param.span);
+                        match &param.type_info {
+                            Some(type_info) => writeln!(
+                                self.out,
+                                "{}Parameter: {} - Type: {}{}",
+                                self.print_indent(),
+                                format_pattern(&param.pattern.kind),
+                                type_info,
+                                param_span
+                            )?,
+                            None => writeln!(
+                                self.out,
+                                "{}Parameter: {}{}",
+                                self.print_indent(),
+                                format_pattern(&param.pattern.kind),
+                                param_span
+                            )?,
+                        }
+                    }
+                    self.indent -= 2;
+                }
+
+                if let Some(ret) = return_type {
+                    writeln!(self.out, "{}Return type: {}", self.print_indent(), ret)?;
+                }
+
+                writeln!(self.out, "{}Body:", self.print_indent())?;
+                self.render_nested_expr(body)?;
+            }
+            ExprJson::MethodCall {
+                receiver,
+                method,
+                turbofish,
+                arguments,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Method call: {}{}",
+                    self.print_indent(),
+                    method,
+                    span
+                )?;
+
+                if !turbofish.is_empty() {
+                    writeln!(
+                        self.out,
+                        "{}Turbofish: {}",
+                        self.print_indent(),
+                        turbofish.join(", ")
+                    )?;
+                }
+
+                writeln!(self.out, "{}Receiver:", self.print_indent())?;
+                self.render_nested_expr(receiver)?;
+
+                if !arguments.is_empty() {
+                    writeln!(self.out, "{}Arguments:", self.print_indent())?;
+                    for arg in arguments {
+                        self.render_nested_expr(arg)?;
                     }
-                    self.indent -= 2;
                 }
             }
-            syn::Expr::Path(expr_path) => {
-                println!(
-                    "{}Identifier: {}",
+            ExprJson::FieldAccess { base, member } => {
+                writeln!(
+                    self.out,
+                    "{}Field access: {}{}",
                     self.print_indent(),
-                    expr_path.to_token_stream()
-                );
+                    member,
+                    span
+                )?;
+                writeln!(self.out, "{}Base:", self.print_indent())?;
+                self.render_nested_expr(base)?;
             }
-            syn::Expr::If(expr_if) => {
-                println!("{}If statement:", self.print_indent());
-
-                println!("{}Condition:", self.print_indent());
-                self.indent += 2;
-                self.visit_expr(&expr_if.cond);
-                self.indent -= 2;
+            ExprJson::Index { base, index } => {
+                writeln!(self.out, "{}Index expression:{}", self.print_indent(), span)?;
 
-                println!("{}Then branch:", self.print_indent());
-                self.indent += 2;
-                for stmt in &expr_if.then_branch.stmts {
-                    self.visit_stmt(stmt);
-                }
-                self.indent -= 2;
+                writeln!(self.out, "{}Base:", self.print_indent())?;
+                self.render_nested_expr(base)?;
 
-                if let Some((_, else_branch)) = &expr_if.else_branch {
-                    println!("{}Else branch:", self.print_indent());
-                    self.indent += 2;
-                    self.visit_expr(else_branch);
-                    self.indent -= 2;
-                }
+                writeln!(self.out, "{}Index:", self.print_indent())?;
+                self.render_nested_expr(index)?;
             }
-            syn::Expr::Loop(expr_loop) => {
-                println!("{}Loop:", self.print_indent());
-                self.indent += 2;
-                for stmt in &expr_loop.body.stmts {
-                    self.visit_stmt(stmt);
-                }
-                self.indent -= 2;
+            ExprJson::Unary {
+                operator, operand, ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Unary expression: {}{}",
+                    self.print_indent(),
+                    operator,
+                    span
+                )?;
+                writeln!(self.out, "{}Operand:", self.print_indent())?;
+                self.render_nested_expr(operand)?;
+            }
+            ExprJson::Reference {
+                mutable,
+                expr: referent,
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Reference: {}{}",
+                    self.print_indent(),
+                    if *mutable { "&mut" } else { "&" },
+                    span
+                )?;
+                self.render_nested_expr(referent)?;
             }
-            syn::Expr::While(expr_while) => {
-                println!("{}While loop:", self.print_indent());
+            ExprJson::Cast {
+                expr: operand,
+                type_info,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Cast: as {}{}",
+                    self.print_indent(),
+                    type_info,
+                    span
+                )?;
+                self.render_nested_expr(operand)?;
+            }
+            ExprJson::Assign { target, value } => {
+                writeln!(self.out, "{}Assignment:{}", self.print_indent(), span)?;
 
-                println!("{}Condition:", self.print_indent());
-                self.indent += 2;
-                self.visit_expr(&expr_while.cond);
-                self.indent -= 2;
+                writeln!(self.out, "{}Target:", self.print_indent())?;
+                self.render_nested_expr(target)?;
 
-                println!("{}Body:", self.print_indent());
-                self.indent += 2;
-                for stmt in &expr_while.body.stmts {
-                    self.visit_stmt(stmt);
-                }
-                self.indent -= 2;
+                writeln!(self.out, "{}Value:", self.print_indent())?;
+                self.render_nested_expr(value)?;
             }
-            syn::Expr::Return(expr_return) => {
-                println!("{}Return statement:", self.print_indent());
-                if let Some(expr) = &expr_return.expr {
-                    self.indent += 2;
-                    self.visit_expr(expr);
-                    self.indent -= 2;
-                }
+            ExprJson::CompoundAssign {
+                operator,
+                target,
+                value,
+                ..
+            } => {
+                writeln!(
+                    self.out,
+                    "{}Compound assignment: {}{}",
+                    self.print_indent(),
+                    operator,
+                    span
+                )?;
+
+                writeln!(self.out, "{}Target:", self.print_indent())?;
+                self.render_nested_expr(target)?;
+
+                writeln!(self.out, "{}Value:", self.print_indent())?;
+                self.render_nested_expr(value)?;
+            }
+            ExprJson::Block { label, body } => {
+                writeln!(
+                    self.out,
+                    "{}Block:{}{}",
+                    self.print_indent(),
+                    self.print_label(label),
+                    span
+                )?;
+                self.render_block(body)?;
+            }
+            ExprJson::AsyncBlock { is_move, body } => {
+                writeln!(
+                    self.out,
+                    "{}Async block:{}{}",
+                    self.print_indent(),
+                    if *is_move { " move" } else { "" },
+                    span
+                )?;
+                self.render_block(body)?;
+            }
+            ExprJson::UnsafeBlock { body } => {
+                writeln!(self.out, "{}Unsafe block:{}", self.print_indent(), span)?;
+                self.render_block(body)?;
             }
-            _ => {
-                println!(
-                    "{}Other expression: {}",
+            ExprJson::ConstBlock { body } => {
+                writeln!(self.out, "{}Const block:{}", self.print_indent(), span)?;
+                self.render_block(body)?;
+            }
+            ExprJson::Other { description } => {
+                writeln!(
+                    self.out,
+                    "{}Other expression: {}{}",
                     self.print_indent(),
-                    node.to_token_stream()
-                );
+                    description,
+                    span
+                )?;
             }
         }
+        Ok(())
+    }
+}
+
+/// implement Visit trait for AstText
+/// Visit trait is defined in syn::visit
+///
+/// Every method converts the visited node into the shared model and renders it.
+/// Write errors are kept and can be retrieved with `take_error()`.
+impl<'ast, W: Write> syn::visit::Visit<'ast> for TextVisitor<W> {
+    /// visit_file is defined in syn::visit::Visit
+    /// visit_file is called when a whole Rust source file is visited
+    ///
+    /// # Arguments
+    /// * `node`: &'ast syn::File
+    ///
+    /// # Returns
+    /// * `()`
+    fn visit_file(&mut self, node: &'ast syn::File) {
+        let result = self.render_file(node);
+        self.record(result);
+    }
+
+    /// visit_item is defined in syn::visit::Visit
+    /// visit_item is called when a Rust item is visited
+    ///
+    /// # Arguments
+    /// * `node`: &'ast syn::Item
+    ///
+    /// # Returns
+    /// * `()`
+    fn visit_item(&mut self, node: &'ast syn::Item) {
+        let result = self.render_item(&build_item(node));
+        self.record(result);
+    }
+
+    /// visit_item_fn is defined in syn::visit::Visit
+    /// visit_item_fn is called when a Rust function definition is visited
+    ///
+    /// # Arguments
+    /// * `node`: &'ast syn::ItemFn
+    ///
+    /// # Returns
+    /// * `()`
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        let result = self.render_item(&build_item_fn(node));
+        self.record(result);
+    }
+
+    /// visit_expr is defined in syn::visit::Visit
+    /// visit_expr is called when a Rust expression is visited
+    ///
+    /// # Arguments
+    /// * `node`: &'ast syn::Expr
+    ///
+    /// # Returns
+    /// * `()`
+    fn visit_expr(&mut self, node: &'ast syn::Expr) {
+        let result = self.render_expr(&build_expr(node));
+        self.record(result);
     }
 
     /// visit_stmt is defined in syn::visit::Visit
@@ -269,84 +1713,8 @@ impl<'ast> syn::visit::Visit<'ast> for TextVisitor {
     /// # Returns
     /// * `()`
     fn visit_stmt(&mut self, node: &'ast syn::Stmt) {
-        match node {
-            syn::Stmt::Local(local) => {
-                println!("{}Variable declaration:", self.print_indent());
-                if let syn::Pat::Ident(pat_ident) = &local.pat {
-                    println!("{}Name: {}", self.print_indent(), pat_ident.ident);
-                }
-
-                if let Some(init) = &local.init {
-                    println!("{}Initializer:", self.print_indent());
-                    self.indent += 2;
-                    self.visit_expr(&init.expr);
-                    self.indent -= 2;
-                }
-            }
-
-            syn::Stmt::Expr(expr, _) => {
-                println!("{}Expression statement:", self.print_indent());
-                self.indent += 2;
-                self.visit_expr(expr);
-                self.indent -= 2;
-            }
-
-            syn::Stmt::Item(item) => match item {
-                syn::Item::Fn(item_fn) => {
-                    self.visit_item_fn(item_fn);
-                }
-                syn::Item::Struct(item_struct) => {
-                    println!("{}Struct: {}", self.print_indent(), item_struct.ident);
-                    if !item_struct.fields.is_empty() {
-                        println!("{}Fields:", self.print_indent());
-                        self.indent += 2;
-                        for field in &item_struct.fields {
-                            if let Some(ident) = &field.ident {
-                                println!(
-                                    "{}Field: {} - Type: {}",
-                                    self.print_indent(),
-                                    ident,
-                                    field.ty.to_token_stream()
-                                );
-                            } else {
-                                println!(
-                                    "{}Tuple field: {}",
-                                    self.print_indent(),
-                                    field.ty.to_token_stream()
-                                );
-                            }
-                        }
-                        self.indent -= 2;
-                    }
-                }
-                syn::Item::Enum(item_enum) => {
-                    println!("{}Enum: {}", self.print_indent(), item_enum.ident);
-                    if !item_enum.variants.is_empty() {
-                        println!("{}Variants:", self.print_indent());
-                        self.indent += 2;
-                        for variant in &item_enum.variants {
-                            println!("{}Variant: {}", self.print_indent(), variant.ident);
-                        }
-                        self.indent -= 2;
-                    }
-                }
-                _ => {
-                    println!(
-                        "{}Other item: {}",
-                        self.print_indent(),
-                        item.to_token_stream()
-                    );
-                }
-            },
-            // TODO: add other statement
-            _ => {
-                println!(
-                    "{}Other statement: {}",
-                    self.print_indent(),
-                    node.to_token_stream()
-                );
-            }
-        }
+        let result = self.render_stmt(&build_stmt(node));
+        self.record(result);
     }
 }
 
@@ -383,23 +1751,188 @@ pub fn parse_rust_file<P: AsRef<Path>>(path: P) -> io::Result<syn::File> {
     Ok(syntax)
 }
 
+/// write ast
+///
+/// # Arguments
+/// * `file`: &File - ast
+/// * `out`: W - the sink to write the text to
+///
+/// # Returns
+/// * `io::Result<()>` - result
+///
+/// # Errors
+/// * `io::Error` - write error
+pub fn write_ast<W: Write>(file: &File, mut out: W) -> io::Result<()> {
+    writeln!(out, "AST for Rust code:")?;
+    TextVisitor::with_writer(out).render_file(file)
+}
+
 /// print ast
 ///
 /// # Arguments
 /// * `file`: &File - ast
 ///
 /// # Returns
-/// * `()`
-pub fn print_ast(file: &File) {
-    println!("AST for Rust code:");
-    let mut visitor = TextVisitor::new();
-    visitor.visit_file(file);
+/// * `io::Result<()>` - result
+///
+/// # Errors
+/// * `io::Error` - error writing to stdout
+pub fn print_ast(file: &File) -> io::Result<()> {
+    write_ast(file, io::stdout().lock())
+}
+
+/// ast to string
+///
+/// # Arguments
+/// * `file`: &File - ast
+///
+/// # Returns
+/// * `String` - the text rendering of the ast, without the heading printed by `print_ast`
+pub fn ast_to_string(file: &File) -> String {
+    let mut visitor = TextVisitor::with_writer(Vec::new());
+    // writing into a Vec<u8> cannot fail
+    let _ = visitor.render_file(file);
+    String::from_utf8_lossy(&visitor.into_inner()).into_owned()
+}
+
+/// fragment to string
+///
+/// # Arguments
+/// * `fragment`: &FragmentJson - the model of a fragment, see `parse_rust_fragment`
+///
+/// # Returns
+/// * `String` - the text rendering of the fragment
+pub fn fragment_to_string(fragment: &FragmentJson) -> String {
+    let mut visitor = TextVisitor::with_writer(Vec::new());
+    // writing into a Vec<u8> cannot fail
+    let _ = visitor.render_fragment(fragment);
+    String::from_utf8_lossy(&visitor.into_inner()).into_owned()
+}
+
+/// format_pattern
+///
+/// # Arguments
+/// * `pattern`: &PatternJson - the pattern to format
+///
+/// # Returns
+/// * `String` - the pattern written back as compact Rust syntax, as in `Some(n @ 1..=9)`
+fn format_pattern(pattern: &PatternJson) -> String {
+    let join = |patterns: &[Node<PatternJson>], separator: &str| {
+        patterns
+            .iter()
+            .map(|p| format_pattern(&p.kind))
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+
+    match pattern {
+        PatternJson::Identifier {
+            name,
+            by_ref,
+            mutable,
+            subpattern,
+        } => {
+            let mut text = String::new();
+            if *by_ref {
+                text.push_str("ref ");
+            }
+            if *mutable {
+                text.push_str("mut ");
+            }
+            text.push_str(name);
+            if let Some(subpattern) = subpattern {
+                text.push_str(" @ ");
+                text.push_str(&format_pattern(&subpattern.kind));
+            }
+            text
+        }
+        PatternJson::Wildcard => "_".to_string(),
+        PatternJson::Rest => "..".to_string(),
+        PatternJson::Literal { value } => format_pattern_expr(&value.kind),
+        PatternJson::Range {
+            start,
+            end,
+            inclusive,
+        } => format!(
+            "{}{}{}",
+            start
+                .as_ref()
+                .map_or(String::new(), |e| format_pattern_expr(&e.kind)),
+            if *inclusive { "..=" } else { ".." },
+            end.as_ref()
+                .map_or(String::new(), |e| format_pattern_expr(&e.kind))
+        ),
+        PatternJson::Path { path } => path.clone(),
+        PatternJson::Tuple { elements } => format!("({})", join(elements, ", ")),
+        PatternJson::TupleStruct { path, elements } => {
+            format!("{}({})", path, join(elements, ", "))
+        }
+        PatternJson::Struct {
+            path,
+            fields,
+            has_rest,
+        } => {
+            let mut parts: Vec<String> = fields
+                .iter()
+                .map(|field| {
+                    if field.shorthand {
+                        format_pattern(&field.pattern.kind)
+                    } else {
+                        format!("{}: {}", field.member, format_pattern(&field.pattern.kind))
+                    }
+                })
+                .collect();
+            if *has_rest {
+                parts.push("..".to_string());
+            }
+            if parts.is_empty() {
+                format!("{} {{}}", path)
+            } else {
+                format!("{} {{ {} }}", path, parts.join(", "))
+            }
+        }
+        PatternJson::Slice { elements } => format!("[{}]", join(elements, ", ")),
+        PatternJson::Reference { mutable, pattern } => format!(
+            "{}{}",
+            if *mutable { "&mut " } else { "&" },
+            format_pattern(&pattern.kind)
+        ),
+        PatternJson::Or { cases } => join(cases, " | "),
+        PatternJson::Other { description } => description.clone(),
+    }
+}
+
+/// format_pattern_expr
+///
+/// # Arguments
+/// * `expr`: &ExprJson - a literal or path used inside a pattern
+///
+/// # Returns
+/// * `String` - the expression as written in the source
+fn format_pattern_expr(expr: &ExprJson) -> String {
+    match expr {
+        ExprJson::IntLiteral { raw, .. }
+        | ExprJson::FloatLiteral { raw, .. }
+        | ExprJson::StringLiteral { raw, .. }
+        | ExprJson::CharLiteral { raw, .. }
+        | ExprJson::ByteLiteral { raw, .. }
+        | ExprJson::ByteStringLiteral { raw, .. }
+        | ExprJson::CStringLiteral { raw, .. }
+        | ExprJson::VerbatimLiteral { raw } => raw.clone(),
+        ExprJson::BoolLiteral { value } => value.to_string(),
+        ExprJson::Identifier { name } => name.clone(),
+        ExprJson::Unary {
+            operator, operand, ..
+        } => format!("{}{}", operator, format_pattern_expr(&operand.kind)),
+        ExprJson::Other { description } => description.clone(),
+        _ => "<expr>".to_string(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
+    use crate::{FragmentKind, parse_rust_fragment};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -649,4 +2182,395 @@ mod tests {
             panic!("Third item is not a function");
         }
     }
+
+    #[test]
+    fn test_ast_to_string() {
+        let source = r#"
+            struct Point {
+                x: f64,
+            }
+
+            fn add(a: i32, b: i32) -> i32 {
+                a + b
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let text = ast_to_string(&file);
+
+        let expected = "\
+Struct: Point
+Fields:
+  Field: x - Type: f64
+Function: add
+  Parameters:
+    Parameter: a - Type: i32
+    Parameter: b - Type: i32
+  Return type: i32
+  Body:
+    Expression statement:
+      Binary expression: +
+      Left:
+        Identifier: a
+      Right:
+        Identifier: b
+";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_write_ast_with_spans() {
+        let file = parse_rust_source("fn f() {}").unwrap();
+
+        let mut visitor = TextVisitor::with_writer(Vec::new()).with_spans(true);
+        visitor.render_file(&file).unwrap();
+        let text = String::from_utf8(visitor.into_inner()).unwrap();
+        assert_eq!(text, "Function: f [1:0-1:9]\n  Body:\n");
+
+        let mut out = Vec::new();
+        write_ast(&file, &mut out).unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .starts_with("AST for Rust code:\n")
+        );
+    }
+
+    #[test]
+    fn test_write_error_is_propagated() {
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("sink closed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let file = parse_rust_source("fn f() {}").unwrap();
+
+        // render_file returns the error directly
+        let mut visitor = TextVisitor::with_writer(FailingWriter);
+        assert!(visitor.render_file(&file).is_err());
+
+        // the Visit implementation keeps it for take_error
+        let mut visitor = TextVisitor::with_writer(FailingWriter);
+        syn::visit::Visit::visit_file(&mut visitor, &file);
+        assert_eq!(visitor.take_error().unwrap().to_string(), "sink closed");
+        assert!(visitor.take_error().is_none());
+    }
+
+    #[test]
+    fn test_ast_to_string_expressions() {
+        let source = r#"
+            fn f(v: Vec<u8>) {
+                match v.len() {
+                    n if n > 1 => |x| x,
+                    _ => &mut v[0],
+                };
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: f
+  Parameters:
+    Parameter: v - Type: Vec<u8>
+  Body:
+    Expression statement:
+      Match expression:
+      Scrutinee:
+        Method call: len
+        Receiver:
+          Identifier: v
+      Arms:
+        Arm: n
+        Guard:
+          Binary expression: >
+          Left:
+            Identifier: n
+          Right:
+            Integer literal: 1
+        Body:
+          Closure:
+          Parameters:
+            Parameter: x
+          Body:
+            Identifier: x
+        Arm: _
+        Body:
+          Reference: &mut
+            Index expression:
+            Base:
+              Identifier: v
+            Index:
+              Integer literal: 0
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_control_flow() {
+        let source = r#"
+            fn f() {
+                'outer: for i in xs {
+                    break 'outer i?;
+                }
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: f
+  Body:
+    Expression statement:
+      For loop: 'outer
+      Pattern: i
+      Iterable:
+        Identifier: xs
+      Body:
+        Expression statement:
+          Break: 'outer
+            Try expression:
+              Identifier: i
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_literals_and_aggregates() {
+        let source = r#"
+            fn f() {
+                let p = P { x: 1u8, ..(base) };
+                let r = ['a'; 2];
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: f
+  Body:
+    Variable declaration:
+    Name: p
+    Initializer:
+      Struct literal: P
+      Fields:
+        Field: x
+          Integer literal: 1 - Suffix: u8
+      Base:
+        Parenthesized:
+          Identifier: base
+    Variable declaration:
+    Name: r
+    Initializer:
+      Array repeat:
+      Value:
+        Char literal: 'a'
+      Length:
+        Integer literal: 2
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_patterns() {
+        let source = r#"
+            fn f((a, _): (u8, u8)) {
+                let Point { x, y: [first, ..] } = p;
+                if let Some(n @ 1..=9) | None = opt {}
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: f
+  Parameters:
+    Parameter: (a, _) - Type: (u8, u8)
+  Body:
+    Variable declaration:
+    Pattern: Point { x, y: [first, ..] }
+    Initializer:
+      Identifier: p
+    Expression statement:
+      If statement:
+      Condition:
+        Let condition: Some(n @ 1..=9) | None
+          Identifier: opt
+      Then branch:
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_generics_on_items() {
+        let source = r#"
+            fn first<'a, T: Clone>(items: &'a [T]) -> &'a T where T: Debug {}
+            struct Pair<A, B = A> { a: A, b: B }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: first
+  Generics:
+    Lifetime parameter: 'a
+    Type parameter: T: Clone
+  Where clause:
+    Predicate: T: Debug
+  Parameters:
+    Parameter: items - Type: &'a [T]
+  Return type: &'a T
+  Body:
+Struct: Pair
+Generics:
+  Type parameter: A
+  Type parameter: B = A
+Fields:
+  Field: a - Type: A
+  Field: b - Type: B
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_attributes() {
+        let source = r#"
+            /// A unit of work
+            #[derive(Debug)]
+            struct Job {
+                #[doc = "The id"]
+                id: u32,
+            }
+            #[path = "other.rs"]
+            mod other;
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Docs: A unit of work
+Attribute: #[derive(Debug)]
+Struct: Job
+Fields:
+  Docs: The id
+  Field: id - Type: u32
+Attribute: #[path = \"other.rs\"]
+Module: other (out-of-line)
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_visibility_and_qualifiers() {
+        let source = r#"
+            pub const unsafe extern "C" fn export() {}
+            extern "C" { fn printf(format: *const u8, ...) -> i32; }
+            pub(crate) struct Point(pub i32, i32);
+            default unsafe impl Send for Point {}
+            pub(super) mod inner {}
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Function: pub const unsafe extern \"C\" export
+  Body:
+Extern block: \"C\"
+Items:
+  Function: printf
+    Parameters:
+      Parameter: format - Type: *const u8
+      Variadic: ...
+    Return type: i32
+Struct: pub(crate) Point (tuple)
+Fields:
+  Tuple field 0: pub i32
+  Tuple field 1: i32
+Impl: default unsafe Send for Point
+Module: pub(super) inner
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_enum_variants() {
+        let source = r#"
+            enum Msg {
+                Move { x: i32 },
+                Write(String),
+                Code = 3,
+            }
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Enum: Msg
+Variants:
+  Variant: Move
+    Fields:
+      Field: x - Type: i32
+  Variant: Write
+    Fields:
+      Tuple field 0: String
+  Variant: Code
+    Discriminant:
+      Integer literal: 3
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_ast_to_string_struct_kinds() {
+        let source = r#"
+            struct Named { pub id: u32 }
+            struct Pair(#[allow(unused)] pub u8, u8);
+            struct Marker;
+        "#;
+
+        let file = parse_rust_source(source).unwrap();
+        let expected = "\
+Struct: Named
+Fields:
+  Field: pub id - Type: u32
+Struct: Pair (tuple)
+Fields:
+  Attribute: #[allow(unused)]
+  Tuple field 0: pub u8
+  Tuple field 1: u8
+Struct: Marker (unit)
+";
+        assert_eq!(ast_to_string(&file), expected);
+    }
+
+    #[test]
+    fn test_fragment_to_string() {
+        let fragment = parse_rust_fragment("a + b * c", FragmentKind::Expr).unwrap();
+        let expected = "\
+Binary expression: +
+Left:
+  Identifier: a
+Right:
+  Binary expression: *
+  Left:
+    Identifier: b
+  Right:
+    Identifier: c
+";
+        assert_eq!(fragment_to_string(&fragment), expected);
+
+        let fragment = parse_rust_fragment("let x = 1; x", FragmentKind::Block).unwrap();
+        let expected = "\
+Block:
+  Variable declaration:
+  Name: x
+  Initializer:
+    Integer literal: 1
+  Expression statement:
+    Identifier: x
+";
+        assert_eq!(fragment_to_string(&fragment), expected);
+
+        let fragment = parse_rust_fragment("&'a mut [u8]", FragmentKind::Type).unwrap();
+        assert_eq!(fragment_to_string(&fragment), "Type: &'a mut [u8]\n");
+    }
 }